@@ -3,9 +3,21 @@ use generate::{
     IntegerGenerator,
     UnsignedIntegerGenerator,
     FromIteratorGenerator,
+    MapGenerator,
     OptionGenerator,
     ResultGenerator,
-    RandGenerator
+    RandGenerator,
+    MappedGenerator,
+    ArrayGenerator,
+    ElementsGenerator,
+    elements,
+    Constant,
+    CharGenerator,
+    ResizedGenerator,
+    SortedGenerator,
+    string_of,
+    resize,
+    sorted
 };
 use shrink::{
     self,
@@ -13,9 +25,21 @@ use shrink::{
     IntegerShrinker,
     UnsignedIntegerShrinker,
     FromIteratorShrinker,
+    MapShrinker,
+    CanonicalShrinker,
+    CanonicalMapShrinker,
+    MappedShrinker,
+    FilteredShrinker,
+    RangeShrinker,
+    RangeInclusiveShrinker,
+    ArrayShrinker,
+    SortedShrinker,
     DefaultShrinker
 };
 
+use num::Zero;
+use num::bigint::{BigInt, BigUint};
+
 use std::collections::{
     BTreeMap,
     BTreeSet,
@@ -25,7 +49,30 @@ use std::collections::{
     LinkedList,
     VecDeque
 };
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::{Ordering, Reverse};
+use std::hash::Hash;
 use std::iter::{FromIterator};
+use std::marker::PhantomData;
+use std::num::{
+    Wrapping,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+};
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
+use std::sync::Arc;
 
 pub trait Arbitrary: Sized + Clone + 'static {
     type Generator: Generator<Output=Self>;
@@ -92,8 +139,8 @@ macro_rules! uint_impls {
     }
 }
 
-int_impls!  {i8, i16, i32, i64, isize}
-uint_impls! {u8, u16, u32, u64, usize}
+int_impls!  {i8, i16, i32, i64, i128, isize, BigInt}
+uint_impls! {u8, u16, u32, u64, u128, usize, BigUint}
 
 macro_rules! generic_impls {
     ($($container:ident < $($placeholder:ident),* >),*) => {
@@ -118,15 +165,57 @@ macro_rules! generic_impls {
 
 generic_impls! {
     Vec<T>,
-    BTreeMap<K, V>,
     BTreeSet<T>,
-    HashMap<K, V>,
-    HashSet<K, V>,
-    BinaryHeap<T>,
     LinkedList<T>,
     VecDeque<T>
 }
 
+impl <T: Arbitrary + Clone + Eq + Hash> Arbitrary for HashSet<T> {
+    type Generator = FromIteratorGenerator<HashSet<T>, T::Generator>;
+    type Shrink = CanonicalShrinker<HashSet<T>, T::Shrink>;
+
+    fn arbitrary() -> Self::Generator {
+        FromIteratorGenerator::new(T::arbitrary())
+    }
+
+    fn shrink() -> Self::Shrink {
+        CanonicalShrinker::by_hash(T::shrink())
+    }
+}
+
+impl <T: Arbitrary + Ord> Arbitrary for BinaryHeap<T> {
+    type Generator = FromIteratorGenerator<BinaryHeap<T>, T::Generator>;
+    type Shrink = CanonicalShrinker<BinaryHeap<T>, T::Shrink>;
+
+    fn arbitrary() -> Self::Generator {
+        FromIteratorGenerator::new(T::arbitrary())
+    }
+
+    fn shrink() -> Self::Shrink {
+        CanonicalShrinker::new(T::shrink())
+    }
+}
+
+macro_rules! map_impl {
+    ($map:ident, $shrink:ident, $shrinker:ident::$constructor:ident, $($bound:tt)+) => {
+        impl <K: Arbitrary + $($bound)+, V: Arbitrary> Arbitrary for $map<K, V> {
+            type Generator = MapGenerator<$map<K, V>, K::Generator, V::Generator>;
+            type Shrink = $shrink<$map<K, V>, K, V::Shrink>;
+
+            fn arbitrary() -> Self::Generator {
+                MapGenerator::new(K::arbitrary(), V::arbitrary())
+            }
+
+            fn shrink() -> Self::Shrink {
+                $shrinker::$constructor(V::shrink())
+            }
+        }
+    }
+}
+
+map_impl! { BTreeMap, MapShrinker, FromIteratorShrinker::map_values, Ord }
+map_impl! { HashMap, CanonicalMapShrinker, CanonicalShrinker::map_values_by_hash, Eq + Hash }
+
 impl <T: Arbitrary> Arbitrary for Option<T> {
     type Generator = OptionGenerator<T::Generator>;
     type Shrink = shrink::Empty<Self>;
@@ -162,6 +251,19 @@ impl Arbitrary for bool {
     }
 }
 
+impl Arbitrary for Ordering {
+    type Generator = ElementsGenerator<Ordering>;
+    type Shrink = DefaultShrinker<Ordering>;
+
+    fn arbitrary() -> Self::Generator {
+        elements(vec![Ordering::Less, Ordering::Equal, Ordering::Greater])
+    }
+
+    fn shrink() -> Self::Shrink {
+        DefaultShrinker::new()
+    }
+}
+
 impl Arbitrary for char {
     type Generator = RandGenerator<char>;
     type Shrink = shrink::Empty<Self>;
@@ -174,12 +276,262 @@ impl Arbitrary for char {
 }
 
 impl Arbitrary for String {
-    type Generator = FromIteratorGenerator<String, <char as Arbitrary>::Generator>;
+    type Generator = FromIteratorGenerator<String, CharGenerator>;
     type Shrink = shrink::Empty<Self>;
 
     fn arbitrary() -> Self::Generator {
-        FromIteratorGenerator::new(char::arbitrary())
+        string_of(CharGenerator::mixed())
     }
 
     #[inline] fn shrink() -> Self::Shrink { shrink::Empty::empty() }
 }
+
+impl <T: Arbitrary, const N: usize> Arbitrary for [T; N] {
+    type Generator = ArrayGenerator<T::Generator, N>;
+    type Shrink = ArrayShrinker<T::Shrink, N>;
+
+    fn arbitrary() -> Self::Generator {
+        ArrayGenerator::new(T::arbitrary())
+    }
+
+    fn shrink() -> Self::Shrink {
+        ArrayShrinker::new(T::shrink())
+    }
+}
+
+/// Implements `Arbitrary` for types that are generated and shrunk as some other `$inner` type,
+/// converting with `$to` and back with `$from`.
+macro_rules! mapped_impls {
+    ($([$($param:tt)*] $ty:ty, $inner:ty, $gen_ty:ty = $gen:expr, $shrink_ty:ty = $shrink:expr, $to:expr, $from:expr);*) => {
+        $(
+            impl <$($param)*> Arbitrary for $ty {
+                type Generator = MappedGenerator<$gen_ty, fn($inner) -> $ty>;
+                type Shrink = MappedShrinker<$shrink_ty, fn($inner) -> $ty, fn(&$ty) -> $inner>;
+
+                fn arbitrary() -> Self::Generator {
+                    $gen.map($to as fn($inner) -> $ty)
+                }
+
+                fn shrink() -> Self::Shrink {
+                    MappedShrinker::new($shrink, $to as fn($inner) -> $ty, $from as fn(&$ty) -> $inner)
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! wrapper_impls {
+    ($([$($param:tt)*] $wrapper:ty, $inner:ty, $to:expr, $from:expr);*) => {
+        mapped_impls! {
+            $([$($param)*] $wrapper, $inner,
+              <$inner as Arbitrary>::Generator = <$inner>::arbitrary(),
+              <$inner as Arbitrary>::Shrink = <$inner>::shrink(),
+              $to, $from);*
+        }
+    }
+}
+
+wrapper_impls! {
+    [T: Arbitrary] Box<T>, T, Box::new, |b: &Box<T>| (**b).clone();
+    [T: Arbitrary] Rc<T>, T, Rc::new, |rc: &Rc<T>| (**rc).clone();
+    [T: Arbitrary] Arc<T>, T, Arc::new, |arc: &Arc<T>| (**arc).clone();
+    [T: Arbitrary + Copy] Cell<T>, T, Cell::new, |cell: &Cell<T>| cell.get();
+    [T: Arbitrary] RefCell<T>, T, RefCell::new, |cell: &RefCell<T>| cell.borrow().clone();
+    [T: Arbitrary] Wrapping<T>, T, Wrapping, |w: &Wrapping<T>| w.0.clone();
+    [T: Arbitrary] Reverse<T>, T, Reverse, |r: &Reverse<T>| r.0.clone();
+    [] Cow<'static, str>, String, Cow::Owned, |s: &Cow<'static, str>| s.to_string()
+}
+
+impl <T: 'static> Arbitrary for PhantomData<T> {
+    type Generator = Constant<PhantomData<T>>;
+    type Shrink = shrink::Empty<Self>;
+
+    fn arbitrary() -> Self::Generator { Constant(PhantomData) }
+
+    #[inline] fn shrink() -> Self::Shrink { shrink::Empty::empty() }
+}
+
+macro_rules! range_impls {
+    ($($range:ident, $shrinker:ident, $from_bounds:expr);*) => {
+        $(
+            impl <T: Arbitrary + PartialOrd> Arbitrary for $range<T> {
+                type Generator = MappedGenerator<(T::Generator, T::Generator), fn((T, T)) -> $range<T>>;
+                type Shrink = $shrinker<T::Shrink>;
+
+                fn arbitrary() -> Self::Generator {
+                    fn ordered<T: PartialOrd>((a, b): (T, T)) -> $range<T> {
+                        match b < a {
+                            true => $from_bounds(b, a),
+                            false => $from_bounds(a, b)
+                        }
+                    }
+                    (T::arbitrary(), T::arbitrary()).map(ordered as fn((T, T)) -> $range<T>)
+                }
+
+                fn shrink() -> Self::Shrink {
+                    $shrinker::new(T::shrink())
+                }
+            }
+        )*
+    }
+}
+
+range_impls! {
+    Range, RangeShrinker, |start, end| start..end;
+    RangeInclusive, RangeInclusiveShrinker, RangeInclusive::new
+}
+
+// Non-zero integers are generated by shifting the non-negative integers up by one, so that zero
+// is stepped over and negative numbers stand for themselves.  They are shrunk with the integer
+// shrinker, leaving out zero, so shrinking heads toward one or minus one.
+fn is_nonzero<T: Zero>(n: &T) -> bool { !n.is_zero() }
+
+macro_rules! nonzero_impls {
+    (signed: $($nonzero:ident $ty:ident),*) => {
+        $(
+            nonzero_impls! { @impl $nonzero, $ty, |n: $ty| if n >= 0 { n.saturating_add(1) } else { n } }
+        )*
+    };
+    (unsigned: $($nonzero:ident $ty:ident),*) => {
+        $(
+            nonzero_impls! { @impl $nonzero, $ty, |n: $ty| n.saturating_add(1) }
+        )*
+    };
+    (@impl $nonzero:ident, $ty:ident, $shift:expr) => {
+        impl Arbitrary for $nonzero {
+            type Generator = MappedGenerator<<$ty as Arbitrary>::Generator, fn($ty) -> $nonzero>;
+            type Shrink = MappedShrinker<
+                FilteredShrinker<<$ty as Arbitrary>::Shrink, fn(&$ty) -> bool>,
+                fn($ty) -> $nonzero,
+                fn(&$nonzero) -> $ty>;
+
+            fn arbitrary() -> Self::Generator {
+                <$ty>::arbitrary().map((|n: $ty| $nonzero::new($shift(n)).unwrap()) as fn($ty) -> $nonzero)
+            }
+
+            fn shrink() -> Self::Shrink {
+                <$ty>::shrink()
+                    .filter(is_nonzero as fn(&$ty) -> bool)
+                    .map(
+                        (|n: $ty| $nonzero::new(n).unwrap()) as fn($ty) -> $nonzero,
+                        (|n: &$nonzero| n.get()) as fn(&$nonzero) -> $ty
+                    )
+            }
+        }
+    }
+}
+
+nonzero_impls! { unsigned: NonZeroU8 u8, NonZeroU16 u16, NonZeroU32 u32, NonZeroU64 u64, NonZeroU128 u128, NonZeroUsize usize }
+nonzero_impls! { signed: NonZeroI8 i8, NonZeroI16 i16, NonZeroI32 i32, NonZeroI64 i64, NonZeroI128 i128, NonZeroIsize isize }
+
+/// A positive integer.  It shrinks toward one, its simplest value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Positive<T>(pub T);
+
+/// An integer that isn't negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonNegative<T>(pub T);
+
+/// An integer that isn't zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonZero<T>(pub T);
+
+/// An integer no larger in magnitude than the square root of the size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Small<T>(pub T);
+
+/// An integer from the whole range of its type, whatever the size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Large<T>(pub T);
+
+/// A collection with at least one element.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmpty<C>(pub C);
+
+/// A collection whose elements are in ascending order.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sorted<C>(pub C);
+
+/// A string of ASCII characters.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ascii(pub String);
+
+fn largest_size(_: usize) -> usize { <usize>::max_value() }
+
+fn square_root_size(size: usize) -> usize { (size as f64).sqrt() as usize }
+
+// Positive integers are generated by shifting the non-negative ones up by one, and shrunk toward
+// one.  Non-zero integers are generated like the `NonZero*` types are, and shrunk without passing
+// through zero.  Small and large integers are generated at a shrunken and an unbounded size.
+macro_rules! int_modifier_impls {
+    ($($ty:ident $nonzero:ident),*) => {
+        $(
+            mapped_impls! {
+                [] Positive<$ty>, $ty,
+                   MappedGenerator<UnsignedIntegerGenerator<$ty>, fn($ty) -> $ty> =
+                       UnsignedIntegerGenerator::new().map((|n: $ty| n.saturating_add(1)) as fn($ty) -> $ty),
+                   <$ty as Arbitrary>::Shrink = <$ty>::shrink().shrink_towards(1),
+                   Positive, |n: &Positive<$ty>| n.0;
+                [] NonNegative<$ty>, $ty,
+                   UnsignedIntegerGenerator<$ty> = UnsignedIntegerGenerator::new(),
+                   <$ty as Arbitrary>::Shrink = <$ty>::shrink(),
+                   NonNegative, |n: &NonNegative<$ty>| n.0;
+                [] NonZero<$ty>, $ty,
+                   MappedGenerator<<$nonzero as Arbitrary>::Generator, fn($nonzero) -> $ty> =
+                       <$nonzero>::arbitrary().map((|n: $nonzero| n.get()) as fn($nonzero) -> $ty),
+                   FilteredShrinker<<$ty as Arbitrary>::Shrink, fn(&$ty) -> bool> =
+                       <$ty>::shrink().filter(is_nonzero as fn(&$ty) -> bool),
+                   NonZero, |n: &NonZero<$ty>| n.0;
+                [] Small<$ty>, $ty,
+                   ResizedGenerator<<$ty as Arbitrary>::Generator, fn(usize) -> usize> =
+                       resize(<$ty>::arbitrary(), square_root_size as fn(usize) -> usize),
+                   <$ty as Arbitrary>::Shrink = <$ty>::shrink(),
+                   Small, |n: &Small<$ty>| n.0;
+                [] Large<$ty>, $ty,
+                   ResizedGenerator<<$ty as Arbitrary>::Generator, fn(usize) -> usize> =
+                       resize(<$ty>::arbitrary(), largest_size as fn(usize) -> usize),
+                   <$ty as Arbitrary>::Shrink = <$ty>::shrink(),
+                   Large, |n: &Large<$ty>| n.0
+            }
+        )*
+    }
+}
+
+int_modifier_impls! {
+    i8 NonZeroI8, i16 NonZeroI16, i32 NonZeroI32, i64 NonZeroI64, i128 NonZeroI128, isize NonZeroIsize,
+    u8 NonZeroU8, u16 NonZeroU16, u32 NonZeroU32, u64 NonZeroU64, u128 NonZeroU128, usize NonZeroUsize
+}
+
+mapped_impls! {
+    [T: Arbitrary] NonEmpty<Vec<T>>, Vec<T>,
+        FromIteratorGenerator<Vec<T>, T::Generator> = FromIteratorGenerator::non_empty(T::arbitrary()),
+        FromIteratorShrinker<Vec<T>, T::Shrink> = FromIteratorShrinker::with_min_len(T::shrink(), 1),
+        NonEmpty, |v: &NonEmpty<Vec<T>>| v.0.clone();
+    [T: Arbitrary + Ord] Sorted<Vec<T>>, Vec<T>,
+        SortedGenerator<<Vec<T> as Arbitrary>::Generator> = sorted(Vec::<T>::arbitrary()),
+        SortedShrinker<T::Shrink> = SortedShrinker::new(T::shrink()),
+        Sorted, |v: &Sorted<Vec<T>>| v.0.clone()
+}
+
+fn ascii_from_chars(chars: Vec<char>) -> Ascii { Ascii(chars.into_iter().collect()) }
+fn ascii_to_chars(s: &Ascii) -> Vec<char> { s.0.chars().collect() }
+
+impl Arbitrary for Ascii {
+    type Generator = MappedGenerator<FromIteratorGenerator<String, CharGenerator>, fn(String) -> Ascii>;
+    type Shrink = MappedShrinker<
+        FromIteratorShrinker<Vec<char>, shrink::Empty<char>>,
+        fn(Vec<char>) -> Ascii,
+        fn(&Ascii) -> Vec<char>>;
+
+    fn arbitrary() -> Self::Generator {
+        string_of(CharGenerator::ascii()).map(Ascii as fn(String) -> Ascii)
+    }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            FromIteratorShrinker::new(shrink::Empty::empty()),
+            ascii_from_chars as fn(Vec<char>) -> Ascii,
+            ascii_to_chars as fn(&Ascii) -> Vec<char>
+        )
+    }
+}
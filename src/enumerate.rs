@@ -0,0 +1,227 @@
+use std::collections::{
+    BTreeSet,
+    LinkedList,
+    VecDeque
+};
+use std::iter::FromIterator;
+
+use num::traits::FromPrimitive;
+
+/// Types whose values can be listed exhaustively up to a given depth, SmallCheck-style.
+///
+/// `enumerate(depth)` returns every value of depth at most `depth`, smallest first.  For
+/// scalars the depth bounds the magnitude.  Tuples, `Option` and `Result` enumerate their
+/// contents at the same depth, while sequences and sets hold at most `depth` elements, each of
+/// depth at most `depth - 1`.  Tuples are listed in order of the total depth of their
+/// components, as SmallCheck does, so the first failure found by an exhaustive check is
+/// already minimal.
+pub trait Enumerate: Sized + Clone + 'static {
+    fn enumerate(depth: usize) -> Vec<Self>;
+}
+
+impl Enumerate for bool {
+    fn enumerate(_: usize) -> Vec<bool> { vec![false, true] }
+}
+
+impl Enumerate for char {
+    fn enumerate(depth: usize) -> Vec<char> {
+        (b'a'..(b'z' + 1)).take(depth + 1).map(|b| b as char).collect()
+    }
+}
+
+macro_rules! int_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Enumerate for $ty {
+                fn enumerate(depth: usize) -> Vec<$ty> {
+                    let mut values = vec![0];
+                    for n in (1..(depth + 1)).map(<$ty>::from_usize).take_while(Option::is_some) {
+                        let n = n.unwrap();
+                        values.push(n);
+                        values.push(-n);
+                    }
+                    values
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! uint_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Enumerate for $ty {
+                fn enumerate(depth: usize) -> Vec<$ty> {
+                    (0..(depth + 1)).map(<$ty>::from_usize).take_while(Option::is_some).map(Option::unwrap).collect()
+                }
+            }
+        )*
+    }
+}
+
+int_impls!  {i8, i16, i32, i64, i128, isize}
+uint_impls! {u8, u16, u32, u64, u128, usize}
+
+impl <T: Enumerate> Enumerate for Option<T> {
+    fn enumerate(depth: usize) -> Vec<Option<T>> {
+        let mut values = vec![None];
+        values.extend(T::enumerate(depth).into_iter().map(Some));
+        values
+    }
+}
+
+impl <TOk: Enumerate, TErr: Enumerate> Enumerate for Result<TOk, TErr> {
+    fn enumerate(depth: usize) -> Vec<Result<TOk, TErr>> {
+        TOk::enumerate(depth).into_iter().map(Ok)
+            .chain(TErr::enumerate(depth).into_iter().map(Err))
+            .collect()
+    }
+}
+
+/// Every sequence of at most `depth` elements, each of depth at most `depth - 1`, shortest first.
+pub fn enumerate_sequences<T: Enumerate>(depth: usize) -> Vec<Vec<T>> {
+    let mut sequences = vec![vec![]];
+    if depth == 0 { return sequences }
+
+    let elements = T::enumerate(depth - 1);
+    let mut previous = vec![vec![]];
+    for _ in 0..depth {
+        let next = previous.iter()
+            .flat_map(|prefix: &Vec<T>| elements.iter().map(move |x| {
+                let mut sequence = prefix.clone();
+                sequence.push(x.clone());
+                sequence
+            }))
+            .collect::<Vec<_>>();
+        sequences.extend(next.iter().cloned());
+        previous = next;
+    }
+    sequences
+}
+
+macro_rules! sequence_impls {
+    ($($container:ident),*) => {
+        $(
+            impl <T: Enumerate> Enumerate for $container<T>
+                where $container<T>: FromIterator<T> + Clone
+            {
+                fn enumerate(depth: usize) -> Vec<$container<T>> {
+                    enumerate_sequences::<T>(depth).into_iter()
+                        .map(|sequence| sequence.into_iter().collect())
+                        .collect()
+                }
+            }
+        )*
+    }
+}
+
+sequence_impls! { Vec, LinkedList, VecDeque }
+
+impl <T: Enumerate + Ord> Enumerate for BTreeSet<T> {
+    fn enumerate(depth: usize) -> Vec<BTreeSet<T>> {
+        let mut sets = enumerate_sequences::<T>(depth).into_iter()
+            .map(|sequence| sequence.into_iter().collect::<BTreeSet<T>>())
+            .collect::<Vec<_>>();
+        sets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        sets.dedup();
+        sets
+    }
+}
+
+impl Enumerate for String {
+    fn enumerate(depth: usize) -> Vec<String> {
+        enumerate_sequences::<char>(depth).into_iter()
+            .map(|sequence| sequence.into_iter().collect())
+            .collect()
+    }
+}
+
+/// Every value of depth at most `depth`, in enumeration order, paired with its own depth: the
+/// smallest depth at which it is enumerated.
+fn enumerate_with_depths<T: Enumerate + PartialEq>(depth: usize) -> Vec<(usize, T)> {
+    let levels = (0..depth).map(T::enumerate).collect::<Vec<_>>();
+    T::enumerate(depth).into_iter()
+        .map(|value| (levels.iter().position(|level| level.contains(&value)).unwrap_or(depth), value))
+        .collect()
+}
+
+macro_rules! tuple_product {
+    ($values:ident, ($($bound:ident),*), ()) => {
+        $values.push((0 $(+ $bound.0)*, ($($bound.1.clone(),)*)));
+    };
+    ($values:ident, ($($bound:ident),*), ($first:ident $(, $rest:ident)*)) => {
+        for $first in $first.iter() {
+            tuple_product!($values, ($($bound,)* $first), ($($rest),*));
+        }
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($name:ident),*) => {
+        impl <$($name: Enumerate + PartialEq),*> Enumerate for ($($name,)*) {
+            #[allow(unused_variables, non_snake_case)]
+            fn enumerate(depth: usize) -> Vec<Self> {
+                let mut values = vec![];
+                $(let $name = enumerate_with_depths::<$name>(depth);)*
+                tuple_product!(values, (), ($($name),*));
+                // The sort is stable, so tuples of the same total depth stay in lexicographic order.
+                values.sort_by_key(|&(total, _)| total);
+                values.into_iter().map(|(_, value)| value).collect()
+            }
+        }
+    }
+}
+
+macro_tuples_impl! {tuple_impls}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerate_ints() {
+        assert_eq!(u8::enumerate(3), vec![0, 1, 2, 3]);
+        assert_eq!(i8::enumerate(2), vec![0, 1, -1, 2, -2]);
+        assert_eq!(u8::enumerate(1000).len(), 256);
+    }
+
+    #[test]
+    fn enumerate_vecs_shortest_first() {
+        assert_eq!(
+            Vec::<bool>::enumerate(2),
+            vec![vec![], vec![false], vec![true],
+                 vec![false, false], vec![false, true], vec![true, false], vec![true, true]]
+        );
+    }
+
+    #[test]
+    fn enumerate_tuples() {
+        assert_eq!(
+            <(bool, Option<bool>)>::enumerate(1),
+            vec![(false, None), (false, Some(false)), (false, Some(true)),
+                 (true, None), (true, Some(false)), (true, Some(true))]
+        );
+        assert_eq!(<()>::enumerate(5), vec![()]);
+    }
+
+    #[test]
+    fn enumerate_wrappers_at_the_same_depth() {
+        assert_eq!(Option::<u8>::enumerate(1), vec![None, Some(0), Some(1)]);
+        assert_eq!(Result::<u8, bool>::enumerate(0), vec![Ok(0), Err(false), Err(true)]);
+        assert_eq!(<(u8, u8)>::enumerate(1), vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn enumerate_tuples_by_total_depth() {
+        assert_eq!(
+            <(u8, u8)>::enumerate(2),
+            vec![(0, 0), (0, 1), (1, 0), (0, 2), (1, 1), (2, 0), (1, 2), (2, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn enumerate_sets_are_distinct() {
+        let sets = BTreeSet::<u8>::enumerate(2);
+        assert_eq!(sets.len(), 4);
+    }
+}
@@ -0,0 +1,378 @@
+use arbitrary::Arbitrary;
+use generate::{Generator, GenerateCtx};
+use shrink::Shrink;
+
+use std::fmt::{self, Debug};
+use std::iter;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use rand::{self, SeedableRng, StdRng};
+
+/// Types that can be mixed into a random seed, so that the values generated from that seed
+/// depend on them.  This is what makes it possible to generate functions of a type.
+pub trait CoArbitrary {
+    fn coarbitrary(&self, seed: &mut Vec<usize>);
+}
+
+/// A random number generator seeded from `seed` and perturbed by `value`.
+pub fn perturb<T: CoArbitrary + ?Sized>(value: &T, seed: usize) -> StdRng {
+    let mut seed = vec![seed];
+    value.coarbitrary(&mut seed);
+    StdRng::from_seed(&seed[..])
+}
+
+macro_rules! int_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl CoArbitrary for $ty {
+                #[inline]
+                fn coarbitrary(&self, seed: &mut Vec<usize>) {
+                    seed.push(*self as usize);
+                }
+            }
+        )*
+    }
+}
+
+int_impls! { i8, i16, i32, isize, u8, u16, u32, usize, bool, char }
+
+macro_rules! int64_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl CoArbitrary for $ty {
+                #[inline]
+                fn coarbitrary(&self, seed: &mut Vec<usize>) {
+                    // Push each half on its own, as `usize` may be narrower than 64 bits.
+                    seed.push(*self as u64 as u32 as usize);
+                    seed.push((*self as u64 >> 32) as usize);
+                }
+            }
+        )*
+    }
+}
+
+int64_impls! { i64, u64 }
+
+macro_rules! int128_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl CoArbitrary for $ty {
+                #[inline]
+                fn coarbitrary(&self, seed: &mut Vec<usize>) {
+                    seed.push(*self as u64 as usize);
+                    seed.push((*self >> 64) as u64 as usize);
+                }
+            }
+        )*
+    }
+}
+
+int128_impls! { i128, u128 }
+
+impl CoArbitrary for str {
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        seed.push(self.len());
+        for c in self.chars() { c.coarbitrary(seed); }
+    }
+}
+
+impl CoArbitrary for String {
+    #[inline]
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        self[..].coarbitrary(seed)
+    }
+}
+
+impl <T: CoArbitrary> CoArbitrary for [T] {
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        seed.push(self.len());
+        for x in self.iter() { x.coarbitrary(seed); }
+    }
+}
+
+impl <T: CoArbitrary> CoArbitrary for Vec<T> {
+    #[inline]
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        self[..].coarbitrary(seed)
+    }
+}
+
+impl <'a, T: CoArbitrary + ?Sized> CoArbitrary for &'a T {
+    #[inline]
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        (*self).coarbitrary(seed)
+    }
+}
+
+impl <T: CoArbitrary> CoArbitrary for Option<T> {
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        match *self {
+            None => seed.push(0),
+            Some(ref t) => { seed.push(1); t.coarbitrary(seed) }
+        }
+    }
+}
+
+impl <TOk: CoArbitrary, TErr: CoArbitrary> CoArbitrary for Result<TOk, TErr> {
+    fn coarbitrary(&self, seed: &mut Vec<usize>) {
+        match *self {
+            Ok(ref t) => { seed.push(0); t.coarbitrary(seed) },
+            Err(ref e) => { seed.push(1); e.coarbitrary(seed) }
+        }
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($name:ident),*) => {
+        impl <$($name: CoArbitrary),*> CoArbitrary for ($($name,)*) {
+            #[allow(unused_variables, non_snake_case)]
+            fn coarbitrary(&self, seed: &mut Vec<usize>) {
+                let ( $(ref $name,)* ) = *self;
+                $($name.coarbitrary(seed);)*
+            }
+        }
+    }
+}
+
+macro_tuples_impl! {tuple_impls}
+
+/// A generated function from `A` to `B`.
+///
+/// A freshly generated `Fun` derives its result for each input from a random seed perturbed by
+/// that input, so it is deterministic per input.  Every input it is applied to is remembered, and
+/// shrinking first replaces the function by a finite lookup table over those inputs with a default
+/// for everything else.  The table is then shrunk by dropping entries, shrinking the results and
+/// shrinking the default, and is what is printed in a counterexample.
+pub struct Fun<A, B> {
+    kind: FunKind<A, B>,
+    applied: Arc<Mutex<Vec<A>>>
+}
+
+#[derive(Clone)]
+enum FunKind<A, B> {
+    Random { seed: usize, size: usize },
+    Table { table: Vec<(A, B)>, default: B }
+}
+
+impl <A, B> Fun<A, B>
+    where A: CoArbitrary + PartialEq + Clone,
+          B: Arbitrary
+{
+    fn new(kind: FunKind<A, B>) -> Self {
+        Fun { kind: kind, applied: Arc::new(Mutex::new(vec![])) }
+    }
+
+    /// A function that maps each input in `table` to its paired output and everything else to
+    /// `default`.
+    pub fn from_table(table: Vec<(A, B)>, default: B) -> Self {
+        Fun::new(FunKind::Table { table: table, default: default })
+    }
+
+    pub fn apply(&self, a: &A) -> B {
+        {
+            let mut applied = self.applied.lock().unwrap();
+            if !applied.contains(a) { applied.push(a.clone()); }
+        }
+        self.evaluate(a)
+    }
+
+    fn evaluate(&self, a: &A) -> B {
+        match self.kind {
+            FunKind::Random { seed, size } => {
+                let mut rng = perturb(a, seed);
+                B::arbitrary().generate(&mut GenerateCtx::new(&mut rng, size))
+            },
+            FunKind::Table { ref table, ref default } =>
+                table.iter()
+                    .find(|&&(ref x, _)| x == a)
+                    .map_or_else(|| default.clone(), |&(_, ref b)| b.clone())
+        }
+    }
+}
+
+impl <A: Clone, B: Clone> Clone for Fun<A, B> {
+    fn clone(&self) -> Self {
+        Fun { kind: self.kind.clone(), applied: self.applied.clone() }
+    }
+}
+
+impl <A: Debug, B: Debug> Debug for Fun<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FunKind::Random { .. } => write!(f, "<function>"),
+            FunKind::Table { ref table, ref default } => {
+                write!(f, "{{")?;
+                for &(ref a, ref b) in table.iter() {
+                    write!(f, "{:?} => {:?}, ", a, b)?;
+                }
+                write!(f, "_ => {:?}}}", default)
+            }
+        }
+    }
+}
+
+pub struct FunGenerator<A, B>(PhantomData<fn() -> (A, B)>);
+
+impl <A, B> FunGenerator<A, B>
+    where FunGenerator<A, B>: Generator
+{
+    pub fn new() -> Self { FunGenerator(PhantomData) }
+}
+
+impl <A, B> Generator for FunGenerator<A, B>
+    where A: CoArbitrary + PartialEq + Clone,
+          B: Arbitrary
+{
+    type Output = Fun<A, B>;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        Fun::new(FunKind::Random { seed: ctx.rng.gen(), size: ctx.size })
+    }
+}
+
+pub struct FunShrinker<A, B>(PhantomData<fn() -> (A, B)>);
+
+impl <A, B> Clone for FunShrinker<A, B> {
+    fn clone(&self) -> Self { FunShrinker(PhantomData) }
+}
+
+impl <A, B> FunShrinker<A, B>
+    where FunShrinker<A, B>: Shrink
+{
+    pub fn new() -> Self { FunShrinker(PhantomData) }
+}
+
+impl <A, B> Shrink for FunShrinker<A, B>
+    where A: CoArbitrary + PartialEq + Clone + 'static,
+          B: Arbitrary
+{
+    type Item = Fun<A, B>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, fun: &Fun<A, B>) -> Self::Iterator {
+        match fun.kind {
+            FunKind::Random { seed, size } => {
+                // The inputs the function is applied to are only known once the property has run,
+                // so the table is built lazily.
+                let fun = fun.clone();
+                Box::new(iter::once(()).map(move |_| {
+                    let table = fun.applied.lock().unwrap().iter()
+                        .map(|a| (a.clone(), fun.evaluate(a)))
+                        .collect();
+                    let mut rng = StdRng::from_seed(&[seed]);
+                    let default = B::arbitrary().generate(&mut GenerateCtx::new(&mut rng, size));
+                    Fun::from_table(table, default)
+                }))
+            },
+            FunKind::Table { ref table, ref default } => {
+                let table = table.clone();
+                let default = default.clone();
+
+                let removals = {
+                    let (table, default) = (table.clone(), default.clone());
+                    (0..table.len()).map(move |idx| {
+                        let mut table = table.clone();
+                        table.remove(idx);
+                        Fun::from_table(table, default.clone())
+                    })
+                };
+                let defaults = {
+                    let table = table.clone();
+                    B::shrink().shrink(&default)
+                        .map(move |default| Fun::from_table(table.clone(), default))
+                };
+                let results = (0..table.len()).flat_map(move |idx| {
+                    let (table, default) = (table.clone(), default.clone());
+                    B::shrink().shrink(&table[idx].1)
+                        .map(move |b| {
+                            let mut table = table.clone();
+                            table[idx].1 = b;
+                            Fun::from_table(table, default.clone())
+                        })
+                });
+
+                Box::new(removals.chain(defaults).chain(results))
+            }
+        }
+    }
+}
+
+impl <A, B> Arbitrary for Fun<A, B>
+    where A: CoArbitrary + PartialEq + Clone + 'static,
+          B: Arbitrary
+{
+    type Generator = FunGenerator<A, B>;
+    type Shrink = FunShrinker<A, B>;
+
+    fn arbitrary() -> Self::Generator { FunGenerator::new() }
+    fn shrink() -> Self::Shrink { FunShrinker::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand;
+
+    #[test]
+    fn fun_is_deterministic() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let f = FunGenerator::<(u8, String), usize>::new().generate(&mut ctx);
+        let x = (3, "abc".to_string());
+        assert_eq!(f.apply(&x), f.clone().apply(&x));
+    }
+
+    #[test]
+    fn fun_shrinks_to_table() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let f = FunGenerator::<u8, u8>::new().generate(&mut ctx);
+        let shrinks = FunShrinker::new().shrink(&f);
+        let results = (f.apply(&1), f.apply(&2));
+
+        let table = shrinks.collect::<Vec<_>>();
+        assert_eq!(table.len(), 1);
+        let default = table[0].apply(&3);
+        assert_eq!(format!("{:?}", table[0]), format!("{{1 => {:?}, 2 => {:?}, _ => {:?}}}", results.0, results.1, default));
+        assert_eq!((table[0].apply(&1), table[0].apply(&2)), results);
+    }
+
+    #[test]
+    fn fun_default_is_generated_at_size() {
+        let mut rng = rand::thread_rng();
+        let defaults = (0..20).map(|_| {
+            let f = FunGenerator::<u8, u64>::new().generate(&mut GenerateCtx::new(&mut rng, 1000));
+            FunShrinker::new().shrink(&f).next().unwrap().apply(&0)
+        }).collect::<Vec<_>>();
+        assert!(defaults.iter().any(|&default| default > 0));
+    }
+
+    #[test]
+    fn fun_is_send() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let mut rng = rand::thread_rng();
+        let f = FunGenerator::<u8, u64>::new().generate(&mut GenerateCtx::new(&mut rng, 1000));
+        assert_send(&f);
+    }
+
+    #[test]
+    fn coarbitrary_uses_both_halves_of_64_bit_integers() {
+        let (mut low, mut high) = (vec![], vec![]);
+        1u64.coarbitrary(&mut low);
+        (1u64 << 32 | 1).coarbitrary(&mut high);
+        assert!(low != high);
+    }
+
+    #[test]
+    fn table_shrinks() {
+        let f = Fun::from_table(vec![(1u8, true), (2u8, false)], true);
+        let shrinks = FunShrinker::new().shrink(&f).map(|f| format!("{:?}", f)).collect::<Vec<_>>();
+        assert_eq!(shrinks, vec![
+            "{2 => false, _ => true}",
+            "{1 => true, _ => true}",
+            "{1 => true, 2 => false, _ => false}",
+            "{1 => false, 2 => false, _ => true}"
+        ]);
+    }
+}
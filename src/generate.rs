@@ -1,10 +1,18 @@
+use std::cmp;
+use std::collections::HashSet;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::iter::FromIterator;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use rand;
 use num::traits::FromPrimitive;
 use num::bigint::{BigInt, BigUint};
 
+use shrink::{Shrink, FromIteratorShrinker};
+
 pub struct GenerateCtx<'a, R: ?Sized + 'a> {
     pub rng: &'a mut R,
     pub size: usize
@@ -19,7 +27,7 @@ impl <'a, R: ?Sized + 'a> GenerateCtx<'a, R> {
     pub fn chop<'b>(&'b mut self) -> GenerateCtx<'b, R>
         where 'a: 'b
     {
-        Self::new(self.rng, self.size/2)
+        GenerateCtx::new(self.rng, self.size/2)
     }
 
     pub fn gen_size(&mut self) -> usize
@@ -37,6 +45,30 @@ pub trait Generator {
     type Output;
 
     fn generate<R: rand::Rng>(&self, &mut GenerateCtx<R>) -> <Self as Generator>::Output;
+
+    fn map<F, T>(self, f: F) -> MappedGenerator<Self, F>
+        where Self: Sized,
+              F: Fn(Self::Output) -> T
+    {
+        MappedGenerator { generator: self, f: f }
+    }
+}
+
+pub struct MappedGenerator<G, F> {
+    generator: G,
+    f: F
+}
+
+impl <G, F, T> Generator for MappedGenerator<G, F>
+    where G: Generator,
+          F: Fn(G::Output) -> T
+{
+    type Output = T;
+
+    #[inline]
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> T {
+        (self.f)(self.generator.generate(ctx))
+    }
 }
 
 impl <'a, G: Generator> Generator for &'a G {
@@ -77,11 +109,151 @@ macro_rules! tuple_impls {
 
 macro_tuples_impl!{tuple_impls}
 
-pub struct IntegerGenerator<X>(PhantomData<fn() -> X>);
+pub struct SizedGenerator<F>(F);
+
+impl <F, G> Generator for SizedGenerator<F>
+    where F: Fn(usize) -> G,
+          G: Generator
+{
+    type Output = G::Output;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        (self.0)(ctx.size).generate(ctx)
+    }
+}
+
+/// A generator built from the size of the `GenerateCtx` it is run with.
+pub fn sized<F, G>(f: F) -> SizedGenerator<F>
+    where F: Fn(usize) -> G,
+          G: Generator
+{
+    SizedGenerator(f)
+}
+
+pub struct ResizedGenerator<G, F> {
+    generator: G,
+    resize: F
+}
+
+impl <G, F> Generator for ResizedGenerator<G, F>
+    where G: Generator,
+          F: Fn(usize) -> usize
+{
+    type Output = G::Output;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let new_size = (self.resize)(ctx.size);
+        self.generator.generate(&mut GenerateCtx::new(ctx.rng, new_size))
+    }
+}
+
+/// Run `generator` with the size of the `GenerateCtx` changed by `resize`.
+pub fn resize<G, F>(generator: G, resize: F) -> ResizedGenerator<G, F>
+    where G: Generator,
+          F: Fn(usize) -> usize
+{
+    ResizedGenerator { generator: generator, resize: resize }
+}
+
+/// Generates recursive values such as trees.  See `recursive`.
+pub struct RecursiveGenerator<B, F> {
+    inner: Arc<(B, F)>
+}
+
+impl <B, F> Clone for RecursiveGenerator<B, F> {
+    fn clone(&self) -> Self {
+        RecursiveGenerator { inner: self.inner.clone() }
+    }
+}
+
+/// The generator handed to the closure of a `RecursiveGenerator`, which generates the recursive
+/// value itself at half the current size.
+pub struct SelfGenerator<T> {
+    generate: Rc<Fn(&mut rand::Rng, usize) -> T>
+}
+
+impl <T> Clone for SelfGenerator<T> {
+    fn clone(&self) -> Self {
+        SelfGenerator { generate: self.generate.clone() }
+    }
+}
+
+impl <T> Generator for SelfGenerator<T> {
+    type Output = T;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> T {
+        (self.generate)(ctx.rng, ctx.size / 2)
+    }
+}
+
+fn generate_recursive<B, F, G>(inner: &Arc<(B, F)>, rng: &mut rand::Rng, size: usize) -> B::Output
+    where B: Generator + 'static,
+          F: Fn(SelfGenerator<B::Output>) -> G + 'static,
+          G: Generator<Output=B::Output>
+{
+    let (ref base, ref extend) = **inner;
+    let mut rng = rng;
+    let use_base = size == 0 || rand::Rng::gen::<bool>(&mut rng);
+    let mut ctx = GenerateCtx::new(&mut rng, size);
+    if use_base {
+        return base.generate(&mut ctx);
+    }
+
+    let recurse = inner.clone();
+    let self_generator = SelfGenerator {
+        generate: Rc::new(move |rng: &mut rand::Rng, size| generate_recursive(&recurse, rng, size))
+    };
+    extend(self_generator).generate(&mut ctx)
+}
+
+impl <B, F, G> Generator for RecursiveGenerator<B, F>
+    where B: Generator + 'static,
+          F: Fn(SelfGenerator<B::Output>) -> G + 'static,
+          G: Generator<Output=B::Output>
+{
+    type Output = B::Output;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        generate_recursive(&self.inner, ctx.rng, ctx.size)
+    }
+}
+
+/// Generate recursive values such as trees, ASTs or JSON documents.
+///
+/// `extend` is given a generator of the recursive value itself, and builds a generator for the
+/// recursive cases from it.  At each level either `base` or the generator built by `extend` is
+/// used with even odds, and `base` is always used once the size reaches zero.  The size is halved
+/// at each level of recursion, so generation always terminates.
+pub fn recursive<B, F, G>(base: B, extend: F) -> RecursiveGenerator<B, F>
+    where B: Generator + 'static,
+          F: Fn(SelfGenerator<B::Output>) -> G + 'static,
+          G: Generator<Output=B::Output>
+{
+    RecursiveGenerator { inner: Arc::new((base, extend)) }
+}
+
+/// Generates integers uniformly in `[-size, size]`.
+///
+/// `edge_cases` mixes in edge cases, whatever the size: zero, one, minus one, the extremes of the
+/// type and their neighbours, and powers of two and their neighbours.
+pub struct IntegerGenerator<X> {
+    edge_cases: f64,
+    _marker: PhantomData<fn() -> X>
+}
 
 impl <X> IntegerGenerator<X> where IntegerGenerator<X>: Generator
 {
-    pub fn new() -> Self { IntegerGenerator(PhantomData) }
+    pub fn new() -> Self { IntegerGenerator { edge_cases: 0.0, _marker: PhantomData } }
+
+    /// Mixes in edge cases with `probability`.  A probability of zero, the default, turns them
+    /// off.
+    pub fn edge_cases(self, probability: f64) -> Self {
+        IntegerGenerator { edge_cases: probability, ..self }
+    }
+}
+
+fn gen_edge_case<R: rand::Rng>(ctx: &mut GenerateCtx<R>, probability: f64) -> bool {
+    probability > 0.0 && ctx.rng.gen::<f64>() < probability
 }
 
 macro_rules! int_impls {
@@ -92,6 +264,19 @@ macro_rules! int_impls {
                 type Output = $ty;
 
                 fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> $ty {
+                    if gen_edge_case(ctx, self.edge_cases) {
+                        let bits = (::std::mem::size_of::<$ty>() * 8) as u32;
+                        return match ctx.rng.gen() {
+                            true => *ctx.rng.choose(&[
+                                0, 1, -1, <$ty>::min_value(), <$ty>::min_value() + 1, <$ty>::max_value(), <$ty>::max_value() - 1
+                            ]).unwrap(),
+                            false => {
+                                let power = (1 as $ty) << ctx.rng.gen_range(1, bits - 1);
+                                let n = power.wrapping_add(ctx.rng.gen_range(-1, 2));
+                                if ctx.rng.gen() { n } else { n.wrapping_neg() }
+                            }
+                        }
+                    }
                     if ctx.size == 0 { return 0; }
                     let cast_size = <$ty>::from_usize(ctx.size);
                     let upper_bound = cast_size.and_then(|s| s.checked_add(1));
@@ -108,21 +293,71 @@ macro_rules! int_impls {
 
 int_impls! { i8, i16, i32, i64, isize }
 
+fn gen_u128<R: rand::Rng>(rng: &mut R) -> u128 {
+    ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128
+}
+
+/// A uniformly random `u128` below `bound`, as rand can't generate 128-bit integers itself.
+fn gen_u128_below<R: rand::Rng>(rng: &mut R, bound: u128) -> u128 {
+    if bound <= u64::max_value() as u128 { return rng.gen_range(0, bound as u64) as u128 }
+    let mask = u128::max_value() >> bound.leading_zeros();
+    loop {
+        let n = gen_u128(rng) & mask;
+        if n < bound { return n }
+    }
+}
+
+impl Generator for IntegerGenerator<i128>
+{
+    type Output = i128;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> i128 {
+        if gen_edge_case(ctx, self.edge_cases) {
+            return match ctx.rng.gen() {
+                true => *ctx.rng.choose(&[
+                    0, 1, -1, i128::min_value(), i128::min_value() + 1, i128::max_value(), i128::max_value() - 1
+                ]).unwrap(),
+                false => {
+                    let n = (1i128 << ctx.rng.gen_range(1, 127)) + ctx.rng.gen_range(-1i8, 2) as i128;
+                    if ctx.rng.gen() { n } else { -n }
+                }
+            }
+        }
+        // Every size fits in an i128, so unlike the narrower types the range doesn't overflow into
+        // the whole type at the largest size.  Draw from the whole type there explicitly.
+        if ctx.size == usize::max_value() { return gen_u128(ctx.rng) as i128 }
+        let size = ctx.size as i128;
+        gen_u128_below(ctx.rng, 2 * size as u128 + 1) as i128 - size
+    }
+}
+
 impl Generator for IntegerGenerator<BigInt>
 {
     type Output = BigInt;
 
     #[inline]
     fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> BigInt {
-        BigInt::from_i64(IntegerGenerator::<i64>::new().generate(ctx)).unwrap()
+        let generator = IntegerGenerator::<i64> { edge_cases: self.edge_cases, _marker: PhantomData };
+        BigInt::from_i64(generator.generate(ctx)).unwrap()
     }
 }
 
-pub struct UnsignedIntegerGenerator<X>(PhantomData<fn() -> X>);
+/// Generates integers uniformly in `[0, size]`.  `edge_cases` mixes in edge cases like it does
+/// for `IntegerGenerator`.
+pub struct UnsignedIntegerGenerator<X> {
+    edge_cases: f64,
+    _marker: PhantomData<fn() -> X>
+}
 
 impl <X> UnsignedIntegerGenerator<X> where UnsignedIntegerGenerator<X>: Generator
 {
-    pub fn new() -> Self { UnsignedIntegerGenerator(PhantomData) }
+    pub fn new() -> Self { UnsignedIntegerGenerator { edge_cases: 0.0, _marker: PhantomData } }
+
+    /// Mixes in edge cases with `probability`.  A probability of zero, the default, turns them
+    /// off.
+    pub fn edge_cases(self, probability: f64) -> Self {
+        UnsignedIntegerGenerator { edge_cases: probability, ..self }
+    }
 }
 
 macro_rules! uint_impls {
@@ -133,6 +368,21 @@ macro_rules! uint_impls {
                 type Output = $ty;
 
                 fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> $ty {
+                    if gen_edge_case(ctx, self.edge_cases) {
+                        // Leave out the sign bit of signed types.
+                        let bits = (::std::mem::size_of::<$ty>() * 8) as u32 - (<$ty>::min_value() != 0) as u32;
+                        return match ctx.rng.gen() {
+                            true => *ctx.rng.choose(&[0, 1, <$ty>::max_value(), <$ty>::max_value() - 1]).unwrap(),
+                            false => {
+                                let power = (1 as $ty) << ctx.rng.gen_range(1, bits);
+                                match ctx.rng.gen_range(0, 3) {
+                                    0 => power - 1,
+                                    1 => power,
+                                    _ => power.wrapping_add(1)
+                                }
+                            }
+                        }
+                    }
                     if ctx.size == 0 { return 0; }
                     let upper_bound = <$ty>::from_usize(ctx.size).and_then(|s| s.checked_add(1));
                     match upper_bound {
@@ -147,18 +397,53 @@ macro_rules! uint_impls {
 
 uint_impls! { u8, u16, u32, u64, usize, i8, i16, i32, i64, isize }
 
+macro_rules! uint128_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Generator for UnsignedIntegerGenerator<$ty>
+            {
+                type Output = $ty;
+
+                fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> $ty {
+                    if gen_edge_case(ctx, self.edge_cases) {
+                        // Leave out the sign bit of signed types.
+                        let bits = 128 - (<$ty>::min_value() != 0) as u32;
+                        return match ctx.rng.gen() {
+                            true => *ctx.rng.choose(&[0, 1, <$ty>::max_value(), <$ty>::max_value() - 1]).unwrap(),
+                            false => {
+                                let power = (1 as $ty) << ctx.rng.gen_range(1, bits);
+                                power - 1 + ctx.rng.gen_range(0, 3) as $ty
+                            }
+                        }
+                    }
+                    // As for `IntegerGenerator<i128>`, the largest size stands for the whole type.
+                    if ctx.size == usize::max_value() {
+                        return (gen_u128(ctx.rng) >> (<$ty>::min_value() != 0) as u32) as $ty
+                    }
+                    gen_u128_below(ctx.rng, ctx.size as u128 + 1) as $ty
+                }
+            }
+        )*
+    }
+}
+
+uint128_impls! { u128, i128 }
+
 impl Generator for UnsignedIntegerGenerator<BigUint>
 {
     type Output = BigUint;
 
     #[inline]
     fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> BigUint {
-        BigUint::from_u64(UnsignedIntegerGenerator::<u64>::new().generate(ctx)).unwrap()
+        let generator = UnsignedIntegerGenerator::<u64> { edge_cases: self.edge_cases, _marker: PhantomData };
+        BigUint::from_u64(generator.generate(ctx)).unwrap()
     }
 }
 
 pub struct FromIteratorGenerator<C, G> {
     generator: G,
+    min_len: usize,
+    max_len: Option<usize>,
     _marker: PhantomData<fn() -> C>
 }
 
@@ -166,7 +451,35 @@ impl <C, G> FromIteratorGenerator<C, G>
     where FromIteratorGenerator<C, G>: Generator
 {
     pub fn new(generator: G) -> Self {
-        FromIteratorGenerator { generator: generator, _marker: PhantomData }
+        FromIteratorGenerator { generator: generator, min_len: 0, max_len: None, _marker: PhantomData }
+    }
+
+    /// Generate collections whose number of elements lies in `len`.  Within that range the
+    /// length still grows with the size of the `GenerateCtx`.
+    ///
+    /// Collections that merge equal elements, such as sets, can end up smaller than requested.
+    pub fn with_len(generator: G, len: Range<usize>) -> Self {
+        assert!(len.start < len.end, "The range of lengths must not be empty");
+        FromIteratorGenerator { generator: generator, min_len: len.start, max_len: Some(len.end), _marker: PhantomData }
+    }
+
+    pub fn non_empty(generator: G) -> Self {
+        FromIteratorGenerator { generator: generator, min_len: 1, max_len: None, _marker: PhantomData }
+    }
+
+    pub fn exact_len(generator: G, len: usize) -> Self {
+        FromIteratorGenerator::with_len(generator, len..(len + 1))
+    }
+
+}
+
+/// A length in `min_len..max_len` that grows with the size of `ctx`.
+fn gen_len<R: rand::Rng>(ctx: &mut GenerateCtx<R>, min_len: usize, max_len: Option<usize>) -> usize {
+    let max_len = max_len.map_or(<usize>::max_value(), |max_len| max_len - 1);
+    let upper = cmp::min(max_len, cmp::max(min_len, ctx.size));
+    match upper.checked_add(1) {
+        Some(upper) => ctx.rng.gen_range(min_len, upper),
+        None => cmp::max(min_len, ctx.rng.gen())
     }
 }
 
@@ -177,12 +490,278 @@ impl <C, G> Generator for FromIteratorGenerator<C, G>
     type Output = C;
 
     fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
-        let size = ctx.gen_size();
+        let size = self.gen_len(ctx);
         let mut chopped_ctx = ctx.chop();
         (0..size).map(|_| self.generator.generate(&mut chopped_ctx)).collect()
     }
 }
 
+impl <C, G> FromIteratorGenerator<C, G> {
+    /// A shrinker for the collections this generates, which shrinks their elements with
+    /// `shrinker` and never removes elements below the minimum length.
+    pub fn shrinker<S>(&self, shrinker: S) -> FromIteratorShrinker<C, S>
+        where FromIteratorShrinker<C, S>: Shrink
+    {
+        FromIteratorShrinker::with_min_len(shrinker, self.min_len)
+    }
+
+    fn gen_len<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> usize {
+        match (self.min_len, self.max_len) {
+            (0, None) => ctx.gen_size(),
+            _ => gen_len(ctx, self.min_len, self.max_len)
+        }
+    }
+}
+
+/// A `Vec` of elements from `g` whose length lies in `len`.
+pub fn vec_of<G: Generator>(g: G, len: Range<usize>) -> FromIteratorGenerator<Vec<G::Output>, G> {
+    FromIteratorGenerator::with_len(g, len)
+}
+
+/// Any `FromIterator` collection of elements from `g` whose length lies in `len`.
+pub fn collection_of<C, G>(g: G, len: Range<usize>) -> FromIteratorGenerator<C, G>
+    where G: Generator,
+          C: FromIterator<G::Output>
+{
+    FromIteratorGenerator::with_len(g, len)
+}
+
+pub fn non_empty<C, G>(g: G) -> FromIteratorGenerator<C, G>
+    where G: Generator,
+          C: FromIterator<G::Output>
+{
+    FromIteratorGenerator::non_empty(g)
+}
+
+pub fn exact_len<C, G>(g: G, len: usize) -> FromIteratorGenerator<C, G>
+    where G: Generator,
+          C: FromIterator<G::Output>
+{
+    FromIteratorGenerator::exact_len(g, len)
+}
+
+/// Generates maps, or any other collection of key-value pairs, with distinct keys.
+///
+/// Keys are drawn until the length picked from the size of the `GenerateCtx` is reached, so
+/// duplicate keys don't make the map smaller than requested.  If the keys are drawn from a
+/// small domain the map may still come out smaller, as the number of draws is bounded.
+pub struct MapGenerator<C, GK, GV> {
+    keys: GK,
+    values: GV,
+    len: Option<Range<usize>>,
+    _marker: PhantomData<fn() -> C>
+}
+
+impl <C, GK, GV> MapGenerator<C, GK, GV>
+    where MapGenerator<C, GK, GV>: Generator
+{
+    pub fn new(keys: GK, values: GV) -> Self {
+        MapGenerator { keys: keys, values: values, len: None, _marker: PhantomData }
+    }
+
+    /// Generate maps whose number of entries lies in `len`, as `FromIteratorGenerator::with_len`
+    /// does for other collections.
+    pub fn with_len(keys: GK, values: GV, len: Range<usize>) -> Self {
+        assert!(len.start < len.end, "The range of lengths must not be empty");
+        MapGenerator { keys: keys, values: values, len: Some(len), _marker: PhantomData }
+    }
+}
+
+const MAX_KEY_DRAWS_PER_ENTRY: usize = 10;
+
+impl <C, GK, GV> Generator for MapGenerator<C, GK, GV>
+    where GK: Generator,
+          GV: Generator,
+          GK::Output: PartialEq,
+          C: FromIterator<(GK::Output, GV::Output)>
+{
+    type Output = C;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let size = match self.len {
+            Some(ref len) => gen_len(ctx, len.start, Some(len.end)),
+            None => ctx.gen_size()
+        };
+        let mut chopped_ctx = ctx.chop();
+        let mut keys = Vec::with_capacity(cmp::min(size, 1024));
+        for _ in 0..size.saturating_mul(MAX_KEY_DRAWS_PER_ENTRY) {
+            if keys.len() >= size { break }
+            let key = self.keys.generate(&mut chopped_ctx);
+            if !keys.contains(&key) { keys.push(key); }
+        }
+        keys.into_iter().map(|key| (key, self.values.generate(&mut chopped_ctx))).collect()
+    }
+}
+
+/// Generates collections like a `FromIteratorGenerator`, except that elements whose key was
+/// already drawn are redrawn.  If the elements have too few distinct keys, the collection can
+/// end up shorter than the length the `FromIteratorGenerator` would pick.
+pub struct UniqueGenerator<G, F> {
+    generator: G,
+    key: F
+}
+
+impl <C, G, F, K> Generator for UniqueGenerator<FromIteratorGenerator<C, G>, F>
+    where G: Generator,
+          F: Fn(&G::Output) -> K,
+          K: Hash + Eq,
+          C: FromIterator<G::Output>
+{
+    type Output = C;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let size = self.generator.gen_len(ctx);
+        let mut chopped_ctx = ctx.chop();
+        let mut seen = HashSet::new();
+        let mut values = Vec::with_capacity(cmp::min(size, 1024));
+        for _ in 0..size.saturating_mul(MAX_KEY_DRAWS_PER_ENTRY) {
+            if values.len() >= size { break }
+            let value = self.generator.generator.generate(&mut chopped_ctx);
+            if seen.insert((self.key)(&value)) { values.push(value); }
+        }
+        values.into_iter().collect()
+    }
+}
+
+/// A collection from `generator` whose elements all have distinct keys.
+pub fn unique_by<C, G, F, K>(generator: FromIteratorGenerator<C, G>, key: F) -> UniqueGenerator<FromIteratorGenerator<C, G>, F>
+    where G: Generator,
+          F: Fn(&G::Output) -> K,
+          K: Hash + Eq,
+          C: FromIterator<G::Output>
+{
+    UniqueGenerator { generator: generator, key: key }
+}
+
+/// A collection from `generator` whose elements are all distinct.
+pub fn unique<C, G, T>(generator: FromIteratorGenerator<C, G>) -> UniqueGenerator<FromIteratorGenerator<C, G>, fn(&T) -> T>
+    where G: Generator<Output=T>,
+          T: Hash + Eq + Clone,
+          C: FromIterator<T>
+{
+    unique_by(generator, T::clone as fn(&T) -> T)
+}
+
+pub struct SortedGenerator<G>(G);
+
+impl <G, T> Generator for SortedGenerator<FromIteratorGenerator<Vec<T>, G>>
+    where G: Generator<Output=T>,
+          T: Ord
+{
+    type Output = Vec<T>;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let mut values = self.0.generate(ctx);
+        values.sort();
+        values
+    }
+}
+
+/// A `Vec` from `generator` sorted in ascending order.
+pub fn sorted<G, T>(generator: FromIteratorGenerator<Vec<T>, G>) -> SortedGenerator<FromIteratorGenerator<Vec<T>, G>>
+    where G: Generator<Output=T>,
+          T: Ord
+{
+    SortedGenerator(generator)
+}
+
+/// Picks elements of `source`, preserving their order.  The number of elements picked grows
+/// with the size of the `GenerateCtx` as the length of a `FromIteratorGenerator` does.
+pub struct SubsetGenerator<C> {
+    source: C
+}
+
+impl <C, T> Generator for SubsetGenerator<C>
+    where C: Clone + IntoIterator<Item=T> + FromIterator<T>
+{
+    type Output = C;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let elements = self.source.clone().into_iter().collect::<Vec<_>>();
+        let len = gen_len(ctx, 0, Some(elements.len() + 1));
+        let mut picked = (0..elements.len()).collect::<Vec<_>>();
+        ctx.rng.shuffle(&mut picked);
+        picked.truncate(len);
+        picked.sort();
+        let mut picked = picked.into_iter().peekable();
+        elements.into_iter().enumerate()
+            .filter(|&(idx, _)| match picked.peek() {
+                Some(&next) if next == idx => { picked.next(); true },
+                _ => false
+            })
+            .map(|(_, x)| x)
+            .collect()
+    }
+}
+
+/// An order-preserving selection of the elements of `source`.
+pub fn sublist_of<T: Clone>(source: &[T]) -> SubsetGenerator<Vec<T>> {
+    SubsetGenerator { source: source.to_vec() }
+}
+
+/// A subset of `source`, which may be any collection such as a `BTreeSet` or `HashSet`.
+pub fn subset_of<C, T>(source: C) -> SubsetGenerator<C>
+    where C: Clone + IntoIterator<Item=T> + FromIterator<T>
+{
+    SubsetGenerator { source: source }
+}
+
+pub struct PermutationGenerator<T> {
+    source: Vec<T>
+}
+
+impl <T: Clone> Generator for PermutationGenerator<T> {
+    type Output = Vec<T>;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        let mut values = self.source.clone();
+        ctx.rng.shuffle(&mut values);
+        values
+    }
+}
+
+/// A random reordering of `source`.
+pub fn permutation_of<T: Clone>(source: Vec<T>) -> PermutationGenerator<T> {
+    PermutationGenerator { source: source }
+}
+
+/// Generates arrays of `N` elements, each from `generator` at the full size.
+#[derive(Clone)]
+pub struct ArrayGenerator<G, const N: usize> {
+    generator: G
+}
+
+impl <G: Generator, const N: usize> ArrayGenerator<G, N> {
+    pub fn new(generator: G) -> Self { ArrayGenerator { generator: generator } }
+}
+
+impl <G: Generator, const N: usize> Generator for ArrayGenerator<G, N> {
+    type Output = [G::Output; N];
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Self::Output {
+        ::std::array::from_fn(|_| self.generator.generate(ctx))
+    }
+}
+
+/// Generates one of a fixed list of values, uniformly.
+#[derive(Clone)]
+pub struct ElementsGenerator<T> {
+    elements: Arc<Vec<T>>
+}
+
+impl <T: Clone> Generator for ElementsGenerator<T> {
+    type Output = T;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> T {
+        ctx.rng.choose(&self.elements).unwrap().clone()
+    }
+}
+
+pub fn elements<T: Clone>(elements: Vec<T>) -> ElementsGenerator<T> {
+    assert!(!elements.is_empty(), "elements needs at least one element");
+    ElementsGenerator { elements: Arc::new(elements) }
+}
+
 pub struct OptionGenerator<G> {
     generator: G
 }
@@ -244,6 +823,103 @@ impl <T: rand::Rand> Generator for RandGenerator<T> {
     }
 }
 
+/// Generates characters drawn from a chosen character class.
+///
+/// `ascii`, `alphanumeric`, `printable`, `ranges` and `one_of` draw uniformly from a fixed set of
+/// characters, and `any` from every Unicode scalar value.  `nasty_unicode` favours characters
+/// that tend to break text handling: combining marks, right-to-left text and overrides,
+/// zero-width characters, and the code points around the surrogates and at the ends of the
+/// planes.  `mixed` mostly draws printable ASCII, with whitespace, control characters, nasty
+/// unicode and arbitrary scalar values mixed in.
+#[derive(Clone, Debug)]
+pub struct CharGenerator {
+    kind: CharKind
+}
+
+#[derive(Clone, Debug)]
+enum CharKind {
+    Ranges(Arc<Vec<(char, char)>>),
+    Any,
+    Nasty,
+    Mixed
+}
+
+const WHITESPACE_AND_CONTROL: &'static [(char, char)] = &[
+    ('\u{0}', '\u{1f}'), (' ', ' '), ('\u{7f}', '\u{7f}'), ('\u{85}', '\u{85}'), ('\u{a0}', '\u{a0}'),
+    ('\u{2028}', '\u{2029}'), ('\u{3000}', '\u{3000}')
+];
+
+const NASTY_UNICODE: &'static [(char, char)] = &[
+    // Combining marks
+    ('\u{300}', '\u{36f}'), ('\u{20d0}', '\u{20ff}'),
+    // Right-to-left scripts, marks and overrides
+    ('\u{590}', '\u{6ff}'), ('\u{200e}', '\u{200f}'), ('\u{202a}', '\u{202e}'), ('\u{2066}', '\u{2069}'),
+    // Zero-width characters, variation selectors and the byte order mark
+    ('\u{200b}', '\u{200d}'), ('\u{2060}', '\u{2060}'), ('\u{fe00}', '\u{fe0f}'), ('\u{feff}', '\u{feff}'),
+    // Around the surrogates, the replacement character, noncharacters and the ends of the planes
+    ('\u{d7ff}', '\u{d7ff}'), ('\u{e000}', '\u{e000}'), ('\u{fffd}', '\u{ffff}'),
+    ('\u{10000}', '\u{10000}'), ('\u{1f600}', '\u{1f64f}'), ('\u{10ffff}', '\u{10ffff}')
+];
+
+impl CharGenerator {
+    fn from_kind(kind: CharKind) -> Self { CharGenerator { kind: kind } }
+
+    pub fn ascii() -> Self { CharGenerator::ranges(vec![('\u{0}', '\u{7f}')]) }
+    pub fn alphanumeric() -> Self { CharGenerator::ranges(vec![('0', '9'), ('A', 'Z'), ('a', 'z')]) }
+    pub fn printable() -> Self { CharGenerator::ranges(vec![(' ', '~')]) }
+    pub fn any() -> Self { CharGenerator::from_kind(CharKind::Any) }
+    pub fn nasty_unicode() -> Self { CharGenerator::from_kind(CharKind::Nasty) }
+    pub fn mixed() -> Self { CharGenerator::from_kind(CharKind::Mixed) }
+
+    /// Characters from the inclusive `ranges`.  Surrogate code points in a range are skipped.
+    pub fn ranges(ranges: Vec<(char, char)>) -> Self {
+        assert!(ranges.iter().any(|&(lo, hi)| lo <= hi), "CharGenerator needs a non-empty range");
+        CharGenerator::from_kind(CharKind::Ranges(Arc::new(ranges)))
+    }
+
+    /// The characters of `chars`.
+    pub fn one_of(chars: &str) -> Self {
+        CharGenerator::ranges(chars.chars().map(|c| (c, c)).collect())
+    }
+}
+
+fn generate_in_ranges<R: rand::Rng>(rng: &mut R, ranges: &[(char, char)]) -> char {
+    let len = |&(lo, hi): &(char, char)| if lo <= hi { hi as u32 - lo as u32 + 1 } else { 0 };
+    loop {
+        let mut idx = rng.gen_range(0, ranges.iter().map(&len).sum::<u32>());
+        for range in ranges.iter() {
+            if idx < len(range) {
+                if let Some(c) = ::std::char::from_u32(range.0 as u32 + idx) { return c }
+                break;
+            }
+            idx -= len(range);
+        }
+    }
+}
+
+impl Generator for CharGenerator {
+    type Output = char;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> char {
+        match self.kind {
+            CharKind::Ranges(ref ranges) => generate_in_ranges(ctx.rng, ranges),
+            CharKind::Any => ctx.rng.gen(),
+            CharKind::Nasty => generate_in_ranges(ctx.rng, NASTY_UNICODE),
+            CharKind::Mixed => match ctx.rng.gen_range(0, 10) {
+                n if n < 6 => generate_in_ranges(ctx.rng, &[(' ', '~')]),
+                6 => generate_in_ranges(ctx.rng, WHITESPACE_AND_CONTROL),
+                7 | 8 => generate_in_ranges(ctx.rng, NASTY_UNICODE),
+                _ => ctx.rng.gen()
+            }
+        }
+    }
+}
+
+/// Strings of characters drawn from `chars`.
+pub fn string_of(chars: CharGenerator) -> FromIteratorGenerator<String, CharGenerator> {
+    FromIteratorGenerator::new(chars)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +946,147 @@ mod tests {
         rep(&mut || { let n = gen.generate(&mut ctx); assert!((n >= -5) && (n <= 5)); });
     }
 
+    #[test]
+    fn gen_vec_of() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let gen = vec_of(UnsignedIntegerGenerator::<u8>::new(), 3..6);
+        rep(&mut || { let v = gen.generate(&mut ctx); assert!(v.len() >= 3 && v.len() < 6); });
+    }
+
+    #[test]
+    fn gen_non_empty_at_size_zero() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 0);
+        let gen = non_empty::<Vec<u8>, _>(UnsignedIntegerGenerator::<u8>::new());
+        rep(&mut || { assert_eq!(gen.generate(&mut ctx).len(), 1); });
+    }
+
+    #[test]
+    fn gen_exact_len() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 2);
+        let gen = exact_len::<String, _>(RandGenerator::<char>::new(), 7);
+        rep(&mut || { assert_eq!(gen.generate(&mut ctx).chars().count(), 7); });
+    }
+
+    #[test]
+    fn gen_map_reaches_size() {
+        use std::collections::BTreeMap;
+
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 5);
+        // Keys are generated at the chopped size, so draw them from the whole range.
+        let gen = MapGenerator::<BTreeMap<u8, u8>, _, _>::new(RandGenerator::<u8>::new(), UnsignedIntegerGenerator::<u8>::new());
+        let mut lengths = vec![];
+        rep(&mut || lengths.push(gen.generate(&mut ctx).len()));
+        assert!(lengths.iter().all(|&len| len <= 5));
+        assert!(lengths.contains(&5));
+    }
+
+    #[test]
+    fn gen_unique_reaches_len() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let gen = unique(vec_of(RandGenerator::<u8>::new(), 10..11));
+        rep(&mut || {
+            let values = gen.generate(&mut ctx);
+            assert_eq!(values.iter().collect::<HashSet<_>>().len(), 10);
+        });
+    }
+
+    #[test]
+    fn gen_sublist_grows_with_size() {
+        let source = (0..20).collect::<Vec<u8>>();
+        let gen = sublist_of(&source);
+        let mut rng = rand::thread_rng();
+        let mut ctx = GenerateCtx::new(&mut rng, 3);
+        rep(&mut || {
+            let values = gen.generate(&mut ctx);
+            assert!(values.len() <= 3 && values.windows(2).all(|w| w[0] < w[1]));
+        });
+        let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let mut lengths = vec![];
+        rep(&mut || lengths.push(gen.generate(&mut ctx).len()));
+        assert!(lengths.iter().any(|&len| len > 10));
+    }
+
+    #[test]
+    fn gen_map_with_small_key_domain() {
+        use std::collections::HashMap;
+
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let gen = MapGenerator::<HashMap<bool, u8>, _, _>::new(RandGenerator::<bool>::new(), UnsignedIntegerGenerator::<u8>::new());
+        rep(&mut || assert!(gen.generate(&mut ctx).len() <= 2));
+    }
+
+    #[test]
+    fn gen_sized_and_resize() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 7);
+        assert_eq!(sized(|size| Constant(size)).generate(&mut ctx), 7);
+        assert_eq!(resize(sized(|size| Constant(size)), |size| size * 3).generate(&mut ctx), 21);
+    }
+
+    #[test]
+    fn gen_recursive_terminates() {
+        #[derive(Debug)]
+        enum Tree { Leaf, Node(Box<Tree>, Box<Tree>) }
+
+        fn depth(tree: &Tree) -> usize {
+            match *tree {
+                Tree::Leaf => 0,
+                Tree::Node(ref l, ref r) => 1 + ::std::cmp::max(depth(l), depth(r))
+            }
+        }
+
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 1000);
+        let gen = recursive(
+            Constant(()).map(|_| Tree::Leaf),
+            |tree| (tree.clone(), tree).map(|(l, r)| Tree::Node(Box::new(l), Box::new(r)))
+        );
+        rep(&mut || { let tree = gen.generate(&mut ctx); assert!(depth(&tree) <= 10, "{:?}", tree); });
+    }
+
+    #[test]
+    fn gen_char_classes() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let alphanumeric = string_of(CharGenerator::alphanumeric());
+        rep(&mut || { let s = alphanumeric.generate(&mut ctx); assert!(s.chars().all(|c| c.is_ascii_alphanumeric()), "{:?}", s); });
+        let printable = CharGenerator::printable();
+        rep(&mut || { let c = printable.generate(&mut ctx); assert!(c >= ' ' && c <= '~'); });
+        let custom = CharGenerator::ranges(vec![('x', 'z'), ('\u{d7ff}', '\u{e000}')]);
+        rep(&mut || { let c = custom.generate(&mut ctx); assert!("xyz\u{d7ff}\u{e000}".contains(c), "{:?}", c); });
+        let one_of = CharGenerator::one_of("ab");
+        rep(&mut || { let c = one_of.generate(&mut ctx); assert!(c == 'a' || c == 'b'); });
+    }
+
+    #[test]
+    fn gen_mixed_chars_include_ascii_and_nasty_unicode() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let chars = (0..1000).map(|_| CharGenerator::mixed().generate(&mut ctx)).collect::<Vec<_>>();
+        assert!(chars.iter().filter(|c| c.is_ascii_graphic()).count() > 300);
+        assert!(chars.iter().any(|c| c.is_whitespace()));
+        assert!(chars.iter().any(|&c| c >= '\u{300}' && c <= '\u{36f}'));
+    }
+
+    #[test]
+    fn gen_edge_cases() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let uniform = IntegerGenerator::<i32>::new();
+        rep(&mut || { let n = uniform.generate(&mut ctx); assert!(n >= -100 && n <= 100); });
+
+        let mut ctx = GenerateCtx::new(ctx.rng, 0);
+        let edges = (0..1000).map(|_| IntegerGenerator::<i32>::new().edge_cases(1.0).generate(&mut ctx)).collect::<Vec<_>>();
+        assert!(edges.contains(&i32::max_value()) && edges.contains(&i32::min_value()) && edges.contains(&-1));
+        let unsigned = (0..1000).map(|_| UnsignedIntegerGenerator::<i8>::new().edge_cases(1.0).generate(&mut ctx)).collect::<Vec<_>>();
+        assert!(unsigned.contains(&i8::max_value()) && unsigned.iter().all(|&n| n >= 0));
+    }
+
+    #[test]
+    fn gen_128_bit_integers() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 5);
+        let signed = IntegerGenerator::<i128>::new();
+        rep(&mut || { let n = signed.generate(&mut ctx); assert!(n >= -5 && n <= 5); });
+        let unsigned = UnsignedIntegerGenerator::<u128>::new();
+        rep(&mut || { let n = unsigned.generate(&mut ctx); assert!(n <= 5); });
+        assert!(gen_u128_below(ctx.rng, 1 << 100) < 1 << 100);
+    }
+
     fn rep<F>(f: &mut F) where F: FnMut() -> () {
         for _ in 0..100 {
             f()
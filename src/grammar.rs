@@ -0,0 +1,453 @@
+use generate::{Generator, GenerateCtx};
+use shrink::Shrink;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter;
+use std::sync::Arc;
+
+use rand;
+
+/// A symbol on the right hand side of a grammar rule.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Symbol {
+    Terminal(String),
+    NonTerminal(String)
+}
+
+pub fn terminal(text: &str) -> Symbol { Symbol::Terminal(text.to_string()) }
+pub fn nonterminal(name: &str) -> Symbol { Symbol::NonTerminal(name.to_string()) }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GrammarError {
+    /// The BNF text couldn't be parsed.
+    Syntax { line: usize, message: &'static str },
+    /// A nonterminal is used but has no rule.
+    Undefined(String),
+    /// A nonterminal has no finite derivation, so sentences of it can't be generated.
+    NonTerminating(String)
+}
+
+/// Builds a `Grammar` rule by rule.  The start symbol is the nonterminal given to
+/// `Grammar::builder`.
+#[derive(Clone, Debug)]
+pub struct GrammarBuilder {
+    start: String,
+    rules: Vec<(String, Vec<Symbol>)>
+}
+
+impl GrammarBuilder {
+    /// Adds `alternatives` to the rule for `name`.
+    pub fn rule(mut self, name: &str, alternatives: Vec<Vec<Symbol>>) -> Self {
+        self.rules.extend(alternatives.into_iter().map(|alternative| (name.to_string(), alternative)));
+        self
+    }
+
+    pub fn build(self) -> Result<Grammar, GrammarError> {
+        let mut names = vec![self.start.clone()];
+        let mut indices = HashMap::new();
+        indices.insert(self.start.clone(), 0);
+        for &(ref name, _) in self.rules.iter() {
+            if !indices.contains_key(name) {
+                indices.insert(name.clone(), names.len());
+                names.push(name.clone());
+            }
+        }
+
+        let mut rules = vec![vec![]; names.len()];
+        for (name, alternative) in self.rules.into_iter() {
+            let symbols = alternative.into_iter()
+                .map(|symbol| match symbol {
+                    Symbol::Terminal(text) => Ok(Sym::Terminal(text)),
+                    Symbol::NonTerminal(name) => indices.get(&name).cloned()
+                        .map(Sym::NonTerminal)
+                        .ok_or(GrammarError::Undefined(name))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rules[indices[&name]].push(symbols);
+        }
+        if rules[0].is_empty() { return Err(GrammarError::Undefined(self.start)) }
+
+        let heights = min_heights(&rules);
+        if let Some(idx) = heights.iter().position(Option::is_none) {
+            return Err(GrammarError::NonTerminating(names[idx].clone()))
+        }
+
+        Ok(Grammar {
+            inner: Arc::new(GrammarInner {
+                rules: rules,
+                heights: heights.into_iter().map(Option::unwrap).collect()
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Sym {
+    Terminal(String),
+    NonTerminal(usize)
+}
+
+/// The height of the shortest derivation of each nonterminal, or `None` if it has none.
+fn min_heights(rules: &[Vec<Vec<Sym>>]) -> Vec<Option<usize>> {
+    let mut heights = vec![None; rules.len()];
+    loop {
+        let mut changed = false;
+        for (idx, alternatives) in rules.iter().enumerate() {
+            let height = alternatives.iter().filter_map(|alternative| alternative_height(&heights, alternative)).min();
+            if height.is_some() && (heights[idx].is_none() || height < heights[idx]) {
+                heights[idx] = height;
+                changed = true;
+            }
+        }
+        if !changed { return heights }
+    }
+}
+
+fn alternative_height(heights: &[Option<usize>], alternative: &[Sym]) -> Option<usize> {
+    alternative.iter().fold(Some(1), |height, symbol| match *symbol {
+        Sym::Terminal(_) => height,
+        Sym::NonTerminal(idx) => match (height, heights[idx]) {
+            (Some(height), Some(child)) => Some(::std::cmp::max(height, child + 1)),
+            _ => None
+        }
+    })
+}
+
+#[derive(Debug)]
+struct GrammarInner {
+    rules: Vec<Vec<Vec<Sym>>>,
+    heights: Vec<usize>
+}
+
+/// A context-free grammar whose sentences can be generated and shrunk.
+///
+/// Grammars are built either with `Grammar::builder` or from BNF text with `Grammar::from_bnf`.
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    inner: Arc<GrammarInner>
+}
+
+impl Grammar {
+    pub fn builder(start: &str) -> GrammarBuilder {
+        GrammarBuilder { start: start.to_string(), rules: vec![] }
+    }
+
+    /// Parses a grammar written in BNF, such as
+    ///
+    /// ```text
+    /// <expr> ::= <term> | <expr> "+" <term>
+    /// <term> ::= "x" | "(" <expr> ")"
+    /// ```
+    ///
+    /// Nonterminals are written `<name>` and terminals are quoted with `"` or `'`.  A line
+    /// starting with `|` adds alternatives to the rule above it, and lines starting with `#` are
+    /// comments.  The first rule defines the start symbol.
+    pub fn from_bnf(text: &str) -> Result<Grammar, GrammarError> {
+        let mut builder: Option<GrammarBuilder> = None;
+        let mut current: Option<String> = None;
+        for (idx, line) in text.lines().enumerate() {
+            let syntax = |message| GrammarError::Syntax { line: idx + 1, message: message };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+
+            let (name, body) = if line.starts_with('|') {
+                match current {
+                    Some(ref name) => (name.clone(), &line[1..]),
+                    None => return Err(syntax("Alternative without a rule"))
+                }
+            } else {
+                let mut parts = line.splitn(2, "::=");
+                let name = parts.next().unwrap().trim();
+                let body = parts.next().ok_or(syntax("Expected ::="))?;
+                if !(name.starts_with('<') && name.ends_with('>') && name.len() > 2) {
+                    return Err(syntax("Expected <nonterminal> before ::="))
+                }
+                (name[1..(name.len() - 1)].to_string(), body)
+            };
+
+            let alternatives = parse_alternatives(body).map_err(&syntax)?;
+            builder = Some(builder.unwrap_or_else(|| Grammar::builder(&name)).rule(&name, alternatives));
+            current = Some(name);
+        }
+        match builder {
+            Some(builder) => builder.build(),
+            None => Err(GrammarError::Syntax { line: 0, message: "Grammar has no rules" })
+        }
+    }
+
+    pub fn generator(&self) -> GrammarGenerator {
+        GrammarGenerator { grammar: self.clone() }
+    }
+
+    pub fn shrinker(&self) -> GrammarShrinker {
+        GrammarShrinker { grammar: self.clone() }
+    }
+
+    fn generate<R: rand::Rng>(&self, nonterminal: usize, ctx: &mut GenerateCtx<R>) -> Derivation {
+        let grammar = &self.inner;
+        let alternatives = &grammar.rules[nonterminal];
+        let alternative = if ctx.size == 0 {
+            let heights = grammar.heights.iter().cloned().map(Some).collect::<Vec<_>>();
+            let shortest = alternatives.iter().enumerate()
+                .filter(|&(_, alternative)| alternative_height(&heights, alternative) == Some(grammar.heights[nonterminal]))
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            shortest[ctx.rng.gen_range(0, shortest.len())]
+        } else {
+            ctx.rng.gen_range(0, alternatives.len())
+        };
+
+        let size = ctx.size;
+        ctx.size = size / 2;
+        let children = alternatives[alternative].iter()
+            .map(|symbol| match *symbol {
+                Sym::Terminal(ref text) => Child::Terminal(text.clone()),
+                Sym::NonTerminal(idx) => Child::Derivation(self.generate(idx, ctx))
+            })
+            .collect();
+        ctx.size = size;
+
+        Derivation { nonterminal: nonterminal, children: children }
+    }
+
+    /// The shortest derivation of `nonterminal`, always picking the first shortest alternative.
+    fn shortest(&self, nonterminal: usize) -> Derivation {
+        let grammar = &self.inner;
+        let heights = grammar.heights.iter().cloned().map(Some).collect::<Vec<_>>();
+        let alternative = grammar.rules[nonterminal].iter()
+            .find(|alternative| alternative_height(&heights, alternative) == Some(grammar.heights[nonterminal]))
+            .unwrap();
+        Derivation {
+            nonterminal: nonterminal,
+            children: alternative.iter()
+                .map(|symbol| match *symbol {
+                    Sym::Terminal(ref text) => Child::Terminal(text.clone()),
+                    Sym::NonTerminal(idx) => Child::Derivation(self.shortest(idx))
+                })
+                .collect()
+        }
+    }
+}
+
+fn parse_alternatives(body: &str) -> Result<Vec<Vec<Symbol>>, &'static str> {
+    let mut alternatives = vec![vec![]];
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '|' => alternatives.push(vec![]),
+            '<' => {
+                let name = chars.by_ref().take_while(|&c| c != '>').collect::<String>();
+                if name.is_empty() { return Err("Empty or unclosed <nonterminal>") }
+                alternatives.last_mut().unwrap().push(Symbol::NonTerminal(name));
+            },
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(escaped) => text.push(escaped),
+                            None => return Err("Unclosed terminal")
+                        },
+                        Some(other) => text.push(other),
+                        None => return Err("Unclosed terminal")
+                    }
+                }
+                alternatives.last_mut().unwrap().push(Symbol::Terminal(text));
+            },
+            _ => return Err("Expected <nonterminal> or a quoted terminal")
+        }
+    }
+    Ok(alternatives)
+}
+
+/// A derivation of a sentence from a `Grammar`.  It displays as the sentence itself.
+#[derive(Clone, PartialEq)]
+pub struct Derivation {
+    nonterminal: usize,
+    children: Vec<Child>
+}
+
+#[derive(Clone, PartialEq)]
+enum Child {
+    Terminal(String),
+    Derivation(Derivation)
+}
+
+impl Derivation {
+    /// The sub-derivations of this derivation's nonterminal, excluding itself, nearest first.
+    fn descendants_of(&self, nonterminal: usize, found: &mut Vec<Derivation>) {
+        for child in self.children.iter() {
+            if let Child::Derivation(ref derivation) = *child {
+                if derivation.nonterminal == nonterminal { found.push(derivation.clone()); }
+                derivation.descendants_of(nonterminal, found);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for child in self.children.iter() {
+            match *child {
+                Child::Terminal(ref text) => f.write_str(text)?,
+                Child::Derivation(ref derivation) => write!(f, "{}", derivation)?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
+
+/// Generates derivations from the start symbol of a `Grammar`.
+///
+/// As with `recursive`, each level of nesting halves the size of the `GenerateCtx`.  At size zero
+/// only the alternatives leading to the shortest derivations are chosen, so the depth of a
+/// derivation is bounded by the logarithm of the size plus the depth of those shortest
+/// derivations.
+#[derive(Clone)]
+pub struct GrammarGenerator {
+    grammar: Grammar
+}
+
+impl Generator for GrammarGenerator {
+    type Output = Derivation;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Derivation {
+        self.grammar.generate(0, ctx)
+    }
+}
+
+/// Shrinks derivations while keeping them derivations of the same grammar.
+///
+/// Each subtree is replaced by smaller derivations of the same nonterminal: first by its own
+/// subtrees of that nonterminal, then by the shortest derivation of that nonterminal.  After that
+/// the subtrees are shrunk in place.
+#[derive(Clone)]
+pub struct GrammarShrinker {
+    grammar: Grammar
+}
+
+impl Shrink for GrammarShrinker {
+    type Item = Derivation;
+    type Iterator = Box<Iterator<Item=Derivation>>;
+
+    fn shrink(&self, derivation: &Derivation) -> Self::Iterator {
+        let mut replacements = vec![];
+        derivation.descendants_of(derivation.nonterminal, &mut replacements);
+        let shortest = self.grammar.shortest(derivation.nonterminal);
+        if shortest != *derivation && !replacements.contains(&shortest) {
+            replacements.push(shortest);
+        }
+
+        let shrinker = self.clone();
+        let derivation = derivation.clone();
+        let children = (0..derivation.children.len()).flat_map(move |idx| {
+            let derivation = derivation.clone();
+            let shrinks: Box<Iterator<Item=Derivation>> = match derivation.children[idx] {
+                Child::Terminal(_) => Box::new(iter::empty()),
+                Child::Derivation(ref child) => shrinker.shrink(child)
+            };
+            shrinks.map(move |child| {
+                let mut derivation = derivation.clone();
+                derivation.children[idx] = Child::Derivation(child);
+                derivation
+            })
+        });
+
+        Box::new(replacements.into_iter().chain(children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use generate::{Generator, GenerateCtx};
+    use shrink::Shrink;
+    use rand;
+
+    const EXPR: &'static str = "
+        # Sums of parenthesised terms
+        <expr> ::= <term> | <expr> '+' <term>
+        <term> ::= \"x\" | \"(\" <expr> \")\"
+    ";
+
+    fn balanced(s: &str) -> bool {
+        s.chars().try_fold(0i32, |depth, c| match c {
+            '(' => Some(depth + 1),
+            ')' if depth > 0 => Some(depth - 1),
+            ')' => None,
+            _ => Some(depth)
+        }) == Some(0)
+    }
+
+    #[test]
+    fn gen_sentences_are_bounded() {
+        let grammar = Grammar::from_bnf(EXPR).unwrap();
+        let mut rng = rand::thread_rng();
+        let mut ctx = GenerateCtx::new(&mut rng, 0);
+        assert_eq!(grammar.generator().generate(&mut ctx).to_string(), "x");
+
+        // Each level of nesting halves the size, and once it reaches zero only the shortest
+        // alternatives are picked, so no derivation is deeper than the halvings of the size plus
+        // the height of the grammar.
+        fn depth(derivation: &Derivation) -> usize {
+            1 + derivation.children.iter()
+                .map(|child| match *child {
+                    Child::Derivation(ref derivation) => depth(derivation),
+                    Child::Terminal(_) => 0
+                })
+                .max().unwrap_or(0)
+        }
+        let bound = 7 + grammar.inner.heights.iter().cloned().max().unwrap();
+
+        let mut ctx = GenerateCtx::new(&mut rng, 100);
+        for _ in 0..100 {
+            let derivation = grammar.generator().generate(&mut ctx);
+            let sentence = derivation.to_string();
+            assert!(balanced(&sentence) && !sentence.contains("++"), "{}", sentence);
+            assert!(depth(&derivation) <= bound, "{} is deeper than {}", sentence, bound);
+        }
+    }
+
+    #[test]
+    fn builder_matches_bnf() {
+        let grammar = Grammar::builder("expr")
+            .rule("expr", vec![vec![nonterminal("term")], vec![nonterminal("expr"), terminal("+"), nonterminal("term")]])
+            .rule("term", vec![vec![terminal("x")], vec![terminal("("), nonterminal("expr"), terminal(")")]])
+            .build()
+            .unwrap();
+        assert_eq!(grammar.shortest(0).to_string(), "x");
+        assert_eq!(grammar.inner.heights, Grammar::from_bnf(EXPR).unwrap().inner.heights);
+    }
+
+    #[test]
+    fn grammar_errors() {
+        assert_eq!(Grammar::from_bnf("<a> ::= <b>").unwrap_err(), GrammarError::Undefined("b".to_string()));
+        assert_eq!(Grammar::from_bnf("<a> ::= 'x' <a>").unwrap_err(), GrammarError::NonTerminating("a".to_string()));
+        assert_eq!(Grammar::from_bnf("<a> ::= 'x'\n<b> 'y'").unwrap_err(), GrammarError::Syntax { line: 2, message: "Expected ::=" });
+    }
+
+    #[test]
+    fn shrinks_to_derivations_of_same_nonterminal() {
+        let grammar = Grammar::from_bnf(EXPR).unwrap();
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        for _ in 0..20 {
+            let derivation = grammar.generator().generate(&mut ctx);
+            for shrunk in grammar.shrinker().shrink(&derivation).take(100) {
+                let sentence = shrunk.to_string();
+                assert!(balanced(&sentence) && sentence.len() < derivation.to_string().len(), "{}", sentence);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use arbitrary::Arbitrary;
+use generate::{
+    Generator,
+    GenerateCtx,
+    IntegerGenerator,
+    FromIteratorGenerator,
+    MapGenerator
+};
+use shrink::{
+    self,
+    Shrink,
+    IntegerShrinker,
+    FromIteratorShrinker,
+    MapShrinker,
+    DefaultShrinker
+};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter;
+
+use rand;
+
+/// A JSON document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>)
+}
+
+fn write_json_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?
+        }
+    }
+    f.write_str("\"")
+}
+
+/// Renders the document as JSON text.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::String(ref s) => write_json_string(f, s),
+            Json::Array(ref xs) => {
+                f.write_str("[")?;
+                for (idx, x) in xs.iter().enumerate() {
+                    if idx > 0 { f.write_str(",")?; }
+                    write!(f, "{}", x)?;
+                }
+                f.write_str("]")
+            },
+            Json::Object(ref m) => {
+                f.write_str("{")?;
+                for (idx, (k, v)) in m.iter().enumerate() {
+                    if idx > 0 { f.write_str(",")?; }
+                    write_json_string(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Generates JSON documents.
+///
+/// Arrays and objects are nested at most `max_depth` deep and hold at most `max_width` entries.
+/// Within those limits, both the depth and the width grow with the size of the `GenerateCtx`:
+/// each level of nesting halves the size, and no container is wider than the current size.
+#[derive(Copy, Clone)]
+pub struct JsonGenerator {
+    max_depth: usize,
+    max_width: usize
+}
+
+impl JsonGenerator {
+    pub fn new() -> Self {
+        JsonGenerator { max_depth: 4, max_width: 8 }
+    }
+
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        JsonGenerator { max_depth: max_depth, ..self }
+    }
+
+    pub fn max_width(self, max_width: usize) -> Self {
+        JsonGenerator { max_width: max_width, ..self }
+    }
+
+    fn nested(&self) -> Self {
+        JsonGenerator { max_depth: self.max_depth - 1, ..*self }
+    }
+
+    fn generate_number<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> f64 {
+        let integer = IntegerGenerator::<i64>::new().generate(ctx) as f64;
+        match ctx.rng.gen_weighted_bool(4) {
+            true => integer + ctx.rng.gen::<f64>(),
+            false => integer
+        }
+    }
+}
+
+impl Generator for JsonGenerator {
+    type Output = Json;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Json {
+        let kinds = if self.max_depth == 0 || ctx.size == 0 { 4 } else { 6 };
+        match ctx.rng.gen_range(0, kinds) {
+            0 => Json::Null,
+            1 => Json::Bool(ctx.rng.gen()),
+            2 => Json::Number(self.generate_number(ctx)),
+            3 => Json::String(String::arbitrary().generate(ctx)),
+            4 => Json::Array(
+                FromIteratorGenerator::with_len(self.nested(), 0..(self.max_width + 1)).generate(ctx)
+            ),
+            _ => Json::Object(
+                MapGenerator::<BTreeMap<String, Json>, _, _>::with_len(
+                    String::arbitrary(), self.nested(), 0..(self.max_width + 1)
+                ).generate(ctx)
+            )
+        }
+    }
+}
+
+/// Shrinks JSON documents toward their subtrees and toward scalars.
+///
+/// Arrays and objects first shrink to each of their elements and then to `null`, before
+/// dropping and shrinking elements.  Object keys are left alone.  Scalars shrink to `null` and
+/// then toward `false`, zero or the empty string.  Infinite and NaN numbers shrink straight to
+/// zero.
+#[derive(Copy, Clone)]
+pub struct JsonShrinker;
+
+impl JsonShrinker {
+    pub fn new() -> Self { JsonShrinker }
+}
+
+const MAX_EXACT_INTEGER: f64 = 9007199254740992.0;
+
+impl Shrink for JsonShrinker {
+    type Item = Json;
+    type Iterator = Box<Iterator<Item=Json>>;
+
+    fn shrink(&self, value: &Json) -> Self::Iterator {
+        let null = iter::once(Json::Null);
+        match *value {
+            Json::Null => Box::new(iter::empty()),
+            Json::Bool(b) => Box::new(null.chain(DefaultShrinker::<bool>::new().shrink(&b).map(Json::Bool))),
+            // Infinities and NaN aren't generated, but can be built by hand.
+            Json::Number(n) if !n.is_finite() => Box::new(null.chain(iter::once(Json::Number(0.0)))),
+            Json::Number(n) if n.fract() != 0.0 => Box::new(null.chain(iter::once(Json::Number(n.trunc())))),
+            Json::Number(n) if n.abs() < MAX_EXACT_INTEGER =>
+                Box::new(null.chain(IntegerShrinker::<i64>::new().shrink(&(n as i64)).map(|n| Json::Number(n as f64)))),
+            Json::Number(_) => Box::new(null.chain(iter::once(Json::Number(0.0)))),
+            Json::String(ref s) => Box::new(
+                null.chain(
+                    FromIteratorShrinker::<Vec<char>, _>::new(shrink::Empty::<char>::empty())
+                        .shrink(&s.chars().collect())
+                        .map(|chars| Json::String(chars.into_iter().collect()))
+                )
+            ),
+            Json::Array(ref xs) => Box::new(
+                xs.clone().into_iter()
+                    .chain(null)
+                    .chain(FromIteratorShrinker::<Vec<Json>, _>::new(JsonShrinker).shrink(xs).map(Json::Array))
+            ),
+            Json::Object(ref m) => {
+                let shrinker: MapShrinker<BTreeMap<String, Json>, String, JsonShrinker> =
+                    FromIteratorShrinker::map_values(JsonShrinker);
+                Box::new(
+                    m.values().cloned().collect::<Vec<_>>().into_iter()
+                        .chain(null)
+                        .chain(shrinker.shrink(m).map(Json::Object))
+                )
+            }
+        }
+    }
+}
+
+impl Arbitrary for Json {
+    type Generator = JsonGenerator;
+    type Shrink = JsonShrinker;
+
+    fn arbitrary() -> Self::Generator { JsonGenerator::new() }
+    fn shrink() -> Self::Shrink { JsonShrinker::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use generate::{Generator, GenerateCtx};
+    use shrink::Shrink;
+    use rand;
+
+    use std::cmp;
+    use std::f64;
+
+    fn depth(json: &Json) -> usize {
+        match *json {
+            Json::Array(ref xs) => 1 + xs.iter().map(depth).max().unwrap_or(0),
+            Json::Object(ref m) => 1 + m.values().map(depth).max().unwrap_or(0),
+            _ => 0
+        }
+    }
+
+    fn width(json: &Json) -> usize {
+        match *json {
+            Json::Array(ref xs) => cmp::max(xs.len(), xs.iter().map(width).max().unwrap_or(0)),
+            Json::Object(ref m) => cmp::max(m.len(), m.values().map(width).max().unwrap_or(0)),
+            _ => 0
+        }
+    }
+
+    #[test]
+    fn gen_json_within_limits() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 100);
+        let gen = JsonGenerator::new().max_depth(2).max_width(3);
+        for _ in 0..100 {
+            let json = gen.generate(&mut ctx);
+            assert!(depth(&json) <= 2 && width(&json) <= 3, "{}", json);
+        }
+    }
+
+    #[test]
+    fn json_display() {
+        let mut m = BTreeMap::new();
+        m.insert("a\"b".to_string(), Json::Array(vec![Json::Null, Json::Bool(true), Json::Number(1.5)]));
+        assert_eq!(Json::Object(m).to_string(), "{\"a\\\"b\":[null,true,1.5]}");
+    }
+
+    #[test]
+    fn json_shrinks_to_subtrees_first() {
+        let json = Json::Array(vec![Json::Number(3.0), Json::String("x".to_string())]);
+        let shrinks = JsonShrinker.shrink(&json).take(3).collect::<Vec<_>>();
+        assert_eq!(shrinks, vec![Json::Number(3.0), Json::String("x".to_string()), Json::Null]);
+        assert_eq!(JsonShrinker.shrink(&Json::Null).count(), 0);
+    }
+
+    #[test]
+    fn non_finite_numbers_shrink_to_zero() {
+        for &n in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let shrinks = JsonShrinker.shrink(&Json::Number(n)).collect::<Vec<_>>();
+            assert_eq!(shrinks, vec![Json::Null, Json::Number(0.0)]);
+        }
+    }
+}
@@ -12,11 +12,20 @@ mod macros;
 pub mod generate;
 pub mod shrink;
 pub mod arbitrary;
+pub mod enumerate;
+pub mod fun;
+pub mod json;
+pub mod regex;
+pub mod grammar;
+pub mod time;
+pub mod net;
+pub mod path;
 mod quick_fn;
 pub mod rose;
 pub mod property;
 pub mod testable;
 pub mod quick_check;
+pub mod shrink_trace;
 
 pub use quick_check::{quickcheck, quicktest, QuickCheck};
 
@@ -11,6 +11,7 @@ macro_rules! macro_rec {
 macro_rules! macro_tuples_impl {
     ($name: ident) => { macro_rec! {$name (
         TuplesImplA, TuplesImplB, TuplesImplC, TuplesImplD, TuplesImplE, TuplesImplF, TuplesImplG,
-        TuplesImplH, TuplesImplI, TuplesImplJ, TuplesImplK, TuplesImplL
+        TuplesImplH, TuplesImplI, TuplesImplJ, TuplesImplK, TuplesImplL, TuplesImplM, TuplesImplN,
+        TuplesImplO, TuplesImplP
     )}}
 }
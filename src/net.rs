@@ -0,0 +1,219 @@
+use arbitrary::Arbitrary;
+use generate::{Generator, GenerateCtx};
+use shrink::{Shrink, MappedShrinker, ArrayShrinker, UnsignedIntegerShrinker};
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use rand;
+
+/// Generates IPv4 addresses.  Half of them are uniformly random and the other half are notable
+/// addresses: unspecified, loopback, broadcast, private, link-local, shared, documentation and
+/// multicast addresses.
+#[derive(Copy, Clone)]
+pub struct Ipv4AddrGenerator;
+
+const NOTABLE_IPV4: &'static [[u8; 4]] = &[
+    [0, 0, 0, 0], [127, 0, 0, 1], [255, 255, 255, 255], [10, 0, 0, 1], [172, 16, 0, 1],
+    [192, 168, 0, 1], [169, 254, 0, 1], [100, 64, 0, 1], [192, 0, 2, 1], [224, 0, 0, 1]
+];
+
+impl Generator for Ipv4AddrGenerator {
+    type Output = Ipv4Addr;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Ipv4Addr {
+        match ctx.rng.gen() {
+            true => Ipv4Addr::from(*ctx.rng.choose(NOTABLE_IPV4).unwrap()),
+            false => Ipv4Addr::from(ctx.rng.gen::<u32>())
+        }
+    }
+}
+
+fn ipv4_octets(ip: &Ipv4Addr) -> [u8; 4] { ip.octets() }
+
+/// Shrinks IPv4 addresses octet by octet toward `0.0.0.0`.
+pub type Ipv4AddrShrinker = MappedShrinker<
+    ArrayShrinker<UnsignedIntegerShrinker<u8>, 4>,
+    fn([u8; 4]) -> Ipv4Addr,
+    fn(&Ipv4Addr) -> [u8; 4]>;
+
+impl Arbitrary for Ipv4Addr {
+    type Generator = Ipv4AddrGenerator;
+    type Shrink = Ipv4AddrShrinker;
+
+    fn arbitrary() -> Self::Generator { Ipv4AddrGenerator }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            ArrayShrinker::new(UnsignedIntegerShrinker::new()),
+            Ipv4Addr::from as fn([u8; 4]) -> Ipv4Addr,
+            ipv4_octets as fn(&Ipv4Addr) -> [u8; 4]
+        )
+    }
+}
+
+/// Generates IPv6 addresses.  Half of them are uniformly random and the other half are notable
+/// addresses: unspecified, loopback, IPv4-mapped, link-local, unique local, documentation and
+/// multicast addresses.
+#[derive(Copy, Clone)]
+pub struct Ipv6AddrGenerator;
+
+const NOTABLE_IPV6: &'static [[u16; 8]] = &[
+    [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 1], [0, 0, 0, 0, 0, 0xffff, 0x7f00, 1],
+    [0xfe80, 0, 0, 0, 0, 0, 0, 1], [0xfc00, 0, 0, 0, 0, 0, 0, 1], [0x2001, 0xdb8, 0, 0, 0, 0, 0, 1],
+    [0xff02, 0, 0, 0, 0, 0, 0, 1]
+];
+
+impl Generator for Ipv6AddrGenerator {
+    type Output = Ipv6Addr;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Ipv6Addr {
+        match ctx.rng.gen() {
+            true => Ipv6Addr::from(*ctx.rng.choose(NOTABLE_IPV6).unwrap()),
+            false => {
+                let mut segments = [0u16; 8];
+                for segment in segments.iter_mut() { *segment = ctx.rng.gen(); }
+                Ipv6Addr::from(segments)
+            }
+        }
+    }
+}
+
+fn ipv6_segments(ip: &Ipv6Addr) -> [u16; 8] { ip.segments() }
+
+/// Shrinks IPv6 addresses segment by segment toward `::`.
+pub type Ipv6AddrShrinker = MappedShrinker<
+    ArrayShrinker<UnsignedIntegerShrinker<u16>, 8>,
+    fn([u16; 8]) -> Ipv6Addr,
+    fn(&Ipv6Addr) -> [u16; 8]>;
+
+impl Arbitrary for Ipv6Addr {
+    type Generator = Ipv6AddrGenerator;
+    type Shrink = Ipv6AddrShrinker;
+
+    fn arbitrary() -> Self::Generator { Ipv6AddrGenerator }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            ArrayShrinker::new(UnsignedIntegerShrinker::new()),
+            Ipv6Addr::from as fn([u16; 8]) -> Ipv6Addr,
+            ipv6_segments as fn(&Ipv6Addr) -> [u16; 8]
+        )
+    }
+}
+
+/// Generates IPv4 and IPv6 addresses equally often.
+#[derive(Copy, Clone)]
+pub struct IpAddrGenerator;
+
+impl Generator for IpAddrGenerator {
+    type Output = IpAddr;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> IpAddr {
+        match ctx.rng.gen() {
+            true => IpAddr::V4(Ipv4AddrGenerator.generate(ctx)),
+            false => IpAddr::V6(Ipv6AddrGenerator.generate(ctx))
+        }
+    }
+}
+
+/// Shrinks IP addresses without changing their version.
+#[derive(Clone)]
+pub struct IpAddrShrinker {
+    v4: Ipv4AddrShrinker,
+    v6: Ipv6AddrShrinker
+}
+
+impl Shrink for IpAddrShrinker {
+    type Item = IpAddr;
+    type Iterator = Box<Iterator<Item=IpAddr>>;
+
+    fn shrink(&self, ip: &IpAddr) -> Self::Iterator {
+        match *ip {
+            IpAddr::V4(ref ip) => Box::new(self.v4.shrink(ip).map(IpAddr::V4)),
+            IpAddr::V6(ref ip) => Box::new(self.v6.shrink(ip).map(IpAddr::V6))
+        }
+    }
+}
+
+impl Arbitrary for IpAddr {
+    type Generator = IpAddrGenerator;
+    type Shrink = IpAddrShrinker;
+
+    fn arbitrary() -> Self::Generator { IpAddrGenerator }
+
+    fn shrink() -> Self::Shrink {
+        IpAddrShrinker { v4: Ipv4Addr::shrink(), v6: Ipv6Addr::shrink() }
+    }
+}
+
+/// Generates socket addresses from `IpAddrGenerator`.  Half of the ports are uniformly random
+/// and the other half are notable ports, including 0 and 65535.
+#[derive(Copy, Clone)]
+pub struct SocketAddrGenerator;
+
+const NOTABLE_PORTS: &'static [u16] = &[0, 1, 22, 80, 443, 1023, 1024, 8080, 65535];
+
+impl Generator for SocketAddrGenerator {
+    type Output = SocketAddr;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> SocketAddr {
+        let ip = IpAddrGenerator.generate(ctx);
+        let port = match ctx.rng.gen() {
+            true => *ctx.rng.choose(NOTABLE_PORTS).unwrap(),
+            false => ctx.rng.gen()
+        };
+        SocketAddr::new(ip, port)
+    }
+}
+
+fn socket_addr_from_parts((ip, port): (IpAddr, u16)) -> SocketAddr { SocketAddr::new(ip, port) }
+fn socket_addr_to_parts(addr: &SocketAddr) -> (IpAddr, u16) { (addr.ip(), addr.port()) }
+
+/// Shrinks socket addresses by shrinking the address and then the port toward zero.
+pub type SocketAddrShrinker = MappedShrinker<
+    (IpAddrShrinker, UnsignedIntegerShrinker<u16>),
+    fn((IpAddr, u16)) -> SocketAddr,
+    fn(&SocketAddr) -> (IpAddr, u16)>;
+
+impl Arbitrary for SocketAddr {
+    type Generator = SocketAddrGenerator;
+    type Shrink = SocketAddrShrinker;
+
+    fn arbitrary() -> Self::Generator { SocketAddrGenerator }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            (IpAddr::shrink(), UnsignedIntegerShrinker::new()),
+            socket_addr_from_parts as fn((IpAddr, u16)) -> SocketAddr,
+            socket_addr_to_parts as fn(&SocketAddr) -> (IpAddr, u16)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand;
+
+    #[test]
+    fn gen_ip_addrs_include_notable_addresses() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 10);
+        let ips = (0..1000).map(|_| IpAddrGenerator.generate(&mut ctx)).collect::<Vec<_>>();
+        assert!(ips.iter().any(|ip| ip.is_loopback()));
+        assert!(ips.iter().any(|ip| ip.is_ipv4()) && ips.iter().any(|ip| ip.is_ipv6()));
+    }
+
+    #[test]
+    fn ip_addrs_shrink_within_version() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let shrinks = IpAddr::shrink().shrink(&v4).collect::<Vec<_>>();
+        assert_eq!(shrinks[0], IpAddr::V4(Ipv4Addr::new(0, 0, 0, 1)));
+        assert!(shrinks.iter().all(IpAddr::is_ipv4));
+
+        let addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), 80);
+        let shrinks = SocketAddr::shrink().shrink(&addr).collect::<Vec<_>>();
+        assert_eq!(shrinks[0], SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0)), 80));
+        assert_eq!(shrinks[1].port(), 0);
+    }
+}
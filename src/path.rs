@@ -0,0 +1,192 @@
+use arbitrary::Arbitrary;
+use generate::{Generator, GenerateCtx, CharGenerator};
+use shrink::{self, Shrink, FromIteratorShrinker};
+
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+use rand;
+
+#[cfg(unix)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    OsString::from_vec(bytes)
+}
+
+#[cfg(unix)]
+fn os_str_to_bytes(s: &OsStr) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    s.as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    OsString::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(not(unix))]
+fn os_str_to_bytes(s: &OsStr) -> Vec<u8> {
+    s.to_string_lossy().into_owned().into_bytes()
+}
+
+/// Byte sequences that aren't valid UTF-8: stray bytes, a truncated sequence, an encoded
+/// surrogate and an overlong encoding of `/`.
+const INVALID_UTF8: &'static [&'static [u8]] = &[
+    &[0xff], &[0xfe], &[0x80], &[0xc3], &[0xed, 0xa0, 0x80], &[0xc0, 0xaf]
+];
+
+fn generate_bytes<R: rand::Rng>(ctx: &mut GenerateCtx<R>) -> Vec<u8> {
+    let mut bytes = vec![];
+    let chars = CharGenerator::mixed();
+    for _ in 0..ctx.gen_size() {
+        match cfg!(unix) && ctx.rng.gen_weighted_bool(10) {
+            true => bytes.extend_from_slice(ctx.rng.choose(INVALID_UTF8).unwrap()),
+            false => {
+                let c = chars.generate(ctx);
+                bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+/// Generates OS strings of characters from `CharGenerator::mixed`.  On Unix, byte sequences that
+/// aren't valid UTF-8 are mixed in too.
+#[derive(Copy, Clone)]
+pub struct OsStringGenerator;
+
+impl Generator for OsStringGenerator {
+    type Output = OsString;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> OsString {
+        os_string_from_bytes(generate_bytes(ctx))
+    }
+}
+
+/// Shrinks OS strings by removing characters, or bytes if they aren't valid UTF-8.
+#[derive(Copy, Clone)]
+pub struct OsStringShrinker;
+
+impl Shrink for OsStringShrinker {
+    type Item = OsString;
+    type Iterator = Box<Iterator<Item=OsString>>;
+
+    fn shrink(&self, s: &OsString) -> Self::Iterator {
+        match s.to_str() {
+            Some(s) => Box::new(
+                FromIteratorShrinker::<Vec<char>, _>::new(shrink::Empty::<char>::empty())
+                    .shrink(&s.chars().collect())
+                    .map(|chars| OsString::from(chars.into_iter().collect::<String>()))
+            ),
+            None => Box::new(
+                FromIteratorShrinker::<Vec<u8>, _>::new(shrink::Empty::<u8>::empty())
+                    .shrink(&os_str_to_bytes(s))
+                    .map(os_string_from_bytes)
+            )
+        }
+    }
+}
+
+impl Arbitrary for OsString {
+    type Generator = OsStringGenerator;
+    type Shrink = OsStringShrinker;
+
+    fn arbitrary() -> Self::Generator { OsStringGenerator }
+    fn shrink() -> Self::Shrink { OsStringShrinker }
+}
+
+const TRICKY_COMPONENTS: &'static [&'static str] = &[
+    "", ".", "..", "...", " ", "-", "~", "*", ".hidden", "a b", "CON", "nul", "ü", "\u{202e}txt.exe", "a\\b"
+];
+
+/// Generates paths as `/`-separated components, up to half the size of them.  A third of the
+/// components are tricky ones like `..`, `.` and the empty component, so paths can be absolute,
+/// have repeated or trailing separators, and climb out of their directory.  The others are
+/// generated like `OsStringGenerator` generates, without separators.
+#[derive(Copy, Clone)]
+pub struct PathBufGenerator;
+
+impl Generator for PathBufGenerator {
+    type Output = PathBuf;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> PathBuf {
+        let len = ctx.chop().gen_size();
+        let components = (0..len)
+            .map(|_| match ctx.rng.gen_weighted_bool(3) {
+                true => ctx.rng.choose(TRICKY_COMPONENTS).unwrap().as_bytes().to_vec(),
+                false => generate_bytes(&mut ctx.chop()).into_iter().filter(|&b| b != b'/').collect()
+            })
+            .collect::<Vec<_>>();
+        PathBuf::from(os_string_from_bytes(components.join(&b'/')))
+    }
+}
+
+/// Shrinks paths by removing and shrinking their `/`-separated components.
+#[derive(Copy, Clone)]
+pub struct PathBufShrinker;
+
+impl Shrink for PathBufShrinker {
+    type Item = PathBuf;
+    type Iterator = Box<Iterator<Item=PathBuf>>;
+
+    fn shrink(&self, path: &PathBuf) -> Self::Iterator {
+        let bytes = os_str_to_bytes(path.as_os_str());
+        // The empty path has no components, rather than a single empty one.
+        let components = match bytes.is_empty() {
+            true => vec![],
+            false => bytes.split(|&b| b == b'/').map(|component| os_string_from_bytes(component.to_vec())).collect()
+        };
+        Box::new(
+            FromIteratorShrinker::<Vec<OsString>, _>::new(OsStringShrinker)
+                .shrink(&components)
+                .map(|components| {
+                    let components = components.iter().map(|c| os_str_to_bytes(c)).collect::<Vec<_>>();
+                    PathBuf::from(os_string_from_bytes(components.join(&b'/')))
+                })
+        )
+    }
+}
+
+impl Arbitrary for PathBuf {
+    type Generator = PathBufGenerator;
+    type Shrink = PathBufShrinker;
+
+    fn arbitrary() -> Self::Generator { PathBufGenerator }
+    fn shrink() -> Self::Shrink { PathBufShrinker }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand;
+
+    #[test]
+    fn gen_paths_have_tricky_components() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 20);
+        let paths = (0..200).map(|_| PathBufGenerator.generate(&mut ctx)).collect::<Vec<_>>();
+        assert!(paths.iter().any(|p| os_str_to_bytes(p.as_os_str()).starts_with(b"/")));
+        assert!(paths.iter().any(|p| os_str_to_bytes(p.as_os_str()).windows(2).any(|w| w == b"..")));
+        if cfg!(unix) {
+            assert!(paths.iter().any(|p| p.to_str().is_none()));
+        }
+    }
+
+    #[test]
+    fn paths_shrink_by_component() {
+        let shrinks = PathBufShrinker.shrink(&PathBuf::from("/ab/..")).collect::<Vec<_>>();
+        assert!(shrinks.contains(&PathBuf::from("ab/..")));
+        assert!(shrinks.contains(&PathBuf::from("/ab")));
+        assert!(shrinks.contains(&PathBuf::from("/b/..")));
+        assert_eq!(PathBufShrinker.shrink(&PathBuf::new()).count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_os_strings_shrink_by_byte() {
+        let s = os_string_from_bytes(vec![b'a', 0xff]);
+        let shrinks = OsStringShrinker.shrink(&s).collect::<Vec<_>>();
+        assert!(shrinks.contains(&OsString::from("a")));
+        assert!(shrinks.contains(&os_string_from_bytes(vec![0xff])));
+    }
+}
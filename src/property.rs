@@ -1,13 +1,14 @@
 use arbitrary::Arbitrary;
 use generate::{Generator, GenerateCtx};
 use shrink::{self, Shrink};
-use testable::{Testable, TestResult, TestStatus};
+use testable::{Testable, ExhaustiveTestable, TestResult, TestStatus};
+use enumerate::Enumerate;
 use quick_fn::QuickFn;
 use rose::{Rose, GenerateWithRose, RoseTraitMap};
 
 use std::marker::PhantomData;
-use std::fmt::Debug;
-use std::rc::Rc;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
 use rand::Rng;
 
 #[derive(Copy, Clone)]
@@ -19,7 +20,7 @@ pub struct Property<Args> {
 pub struct ForAllProperty<Args, G, S, F> {
     generator: G,
     shrinker: S,
-    f: Rc<F>,
+    f: Arc<F>,
     _marker: PhantomData<Args>
 }
 
@@ -46,6 +47,27 @@ pub struct WhenFn<Args, P, F> {
 #[derive(Copy, Clone)]
 pub struct QuickFnArgs<A>(A);
 
+/// Formats property arguments as a tuple.  The standard library only implements `Debug` for
+/// tuples of up to twelve elements, so properties with more arguments are formatted through this.
+pub trait DebugArgs {
+    fn debug_args(&self) -> String;
+}
+
+struct DebugFields<'a>(&'a [&'a Debug]);
+
+impl <'a> Debug for DebugFields<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("()");
+        }
+        let mut tuple = f.debug_tuple("");
+        for field in self.0 {
+            tuple.field(field);
+        }
+        tuple.finish()
+    }
+}
+
 impl <A: Generator> Generator for QuickFnArgs<A> {
     type Output = QuickFnArgs<A::Output>;
 
@@ -66,7 +88,7 @@ impl <A: Arbitrary> Arbitrary for QuickFnArgs<A> {
     #[inline] fn shrink() -> Self::Shrink { shrink::Empty::empty() }
 }
 
-impl <G, S, T, F, Args: Debug + 'static> Testable for ForAllProperty<QuickFnArgs<Args>, G, S, F>
+impl <G, S, T, F, Args: DebugArgs + 'static> Testable for ForAllProperty<QuickFnArgs<Args>, G, S, F>
     where G: Generator<Output=Args>,
           S: Shrink<Item=Args> + Clone + 'static,
           <S as Shrink>::Iterator: 'static,
@@ -86,13 +108,32 @@ impl <G, S, T, F, Args: Debug + 'static> Testable for ForAllProperty<QuickFnArgs
             self.f.clone(),
             |f, args|
                 TestResult {
-                    input: format!("{:?}", &args),
+                    input: args.debug_args(),
                     status: f.call(args).into()
                 }
         )
      }
 }
 
+impl <G, S, T, F, Args: DebugArgs + Enumerate> ExhaustiveTestable for ForAllProperty<QuickFnArgs<Args>, G, S, F>
+    where G: Generator<Output=Args>,
+          S: Shrink<Item=Args> + Clone + 'static,
+          <S as Shrink>::Iterator: 'static,
+          F: QuickFn<Args, Output=T> + 'static,
+          T: Into<TestStatus>
+{
+    fn test_exhaustive<'a>(&'a self, depth: usize) -> Box<Iterator<Item=TestResult> + 'a> {
+        Box::new(
+            Args::enumerate(depth).into_iter().map(move |args|
+                TestResult {
+                    input: args.debug_args(),
+                    status: self.f.call(args).into()
+                }
+            )
+        )
+    }
+}
+
 impl <G, S, Args> ForAll<QuickFnArgs<Args>, G, S> {
     #[inline]
     pub fn property<F, T>(self, f: F) -> ForAllProperty<QuickFnArgs<Args>, G, S, F>
@@ -104,7 +145,7 @@ impl <G, S, Args> ForAll<QuickFnArgs<Args>, G, S> {
         ForAllProperty {
             generator: self.generator,
             shrinker: self.shrinker,
-            f: Rc::new(f),
+            f: Arc::new(f),
             _marker: PhantomData
         }
     }
@@ -118,6 +159,12 @@ impl <Args: Arbitrary> Property<QuickFnArgs<Args>> {
         Property::<QuickFnArgs<Args>>::for_all_shrink(<Args>::arbitrary(), <Args>::shrink()).property(f)
     }
 
+    pub fn when<P: QuickFn<Args, Output=bool>>(p: P) -> When<QuickFnArgs<Args>, P> {
+        When { predicate: p, _marker: PhantomData }
+    }
+}
+
+impl <Args> Property<QuickFnArgs<Args>> {
     pub fn for_all<G: Generator<Output=Args>>(g: G) -> ForAll<QuickFnArgs<Args>, G, shrink::Empty<Args>> {
         Property::<QuickFnArgs<Args>>::for_all_shrink(g, shrink::Empty::empty())
     }
@@ -132,14 +179,18 @@ impl <Args: Arbitrary> Property<QuickFnArgs<Args>> {
             _marker: PhantomData
         }
     }
-
-    pub fn when<P: QuickFn<Args, Output=bool>>(p: P) -> When<QuickFnArgs<Args>, P> {
-        When { predicate: p, _marker: PhantomData }
-    }
 }
 
 macro_rules! fn_impls {
     ($($ident:ident),*) => {
+        impl <$($ident: Debug),*> DebugArgs for ($($ident,)*) {
+            #[allow(non_snake_case)]
+            fn debug_args(&self) -> String {
+                let ($(ref $ident,)*) = *self;
+                format!("{:?}", DebugFields(&[$($ident as &Debug),*]))
+            }
+        }
+
         impl <G, S, T, F, $($ident: Debug + 'static),*> Testable for ForAllProperty<($($ident,)*), G, S, F>
             where G: Generator<Output=($($ident,)*)>,
                   S: Shrink<Item=($($ident,)*)> + Clone + 'static,
@@ -160,7 +211,7 @@ macro_rules! fn_impls {
                 ).scan(
                     self.f.clone(),
                     |f, args| {
-                        let input = format!("{:?}", &args);
+                        let input = args.debug_args();
                         let ($($ident,)*) = args;
                         TestResult {
                             input: input,
@@ -171,6 +222,28 @@ macro_rules! fn_impls {
             }
         }
 
+        impl <G, S, T, F, $($ident: Debug + Enumerate + PartialEq),*> ExhaustiveTestable for ForAllProperty<($($ident,)*), G, S, F>
+            where G: Generator<Output=($($ident,)*)>,
+                  S: Shrink<Item=($($ident,)*)> + Clone + 'static,
+                  <S as Shrink>::Iterator: 'static,
+                  F: Fn($($ident),*) -> T + 'static,
+                  T: Into<TestStatus>
+        {
+            #[allow(non_snake_case)]
+            fn test_exhaustive<'a>(&'a self, depth: usize) -> Box<Iterator<Item=TestResult> + 'a> {
+                Box::new(
+                    <($($ident,)*) as Enumerate>::enumerate(depth).into_iter().map(move |args| {
+                        let input = args.debug_args();
+                        let ($($ident,)*) = args;
+                        TestResult {
+                            input: input,
+                            status: (self.f)($($ident),*).into()
+                        }
+                    })
+                )
+            }
+        }
+
         impl <G, S, $($ident),*> ForAll<($($ident,)*), G, S> {
             #[inline]
             pub fn property<F, T>(self, f: F) -> ForAllProperty<($($ident,)*), G, S, F>
@@ -181,7 +254,7 @@ macro_rules! fn_impls {
                 ForAllProperty {
                     generator: self.generator,
                     shrinker: self.shrinker,
-                    f: Rc::new(f),
+                    f: Arc::new(f),
                     _marker: PhantomData
                 }
             }
@@ -202,6 +275,12 @@ macro_rules! fn_impls {
                 ).property(f)
             }
 
+            pub fn when<P: Fn($($ident),*) -> bool>(p: P) -> When<($($ident,)*), P> {
+                When { predicate: p, _marker: PhantomData }
+            }
+        }
+
+        impl <$($ident),*> Property<($($ident,)*)> {
             pub fn for_all<G>(g: G) -> ForAll<($($ident,)*), G, shrink::Empty<($($ident,)*)>>
                 where G: Generator<Output=($($ident,)*)>
             {
@@ -218,10 +297,6 @@ macro_rules! fn_impls {
                     _marker: PhantomData
                 }
             }
-
-            pub fn when<P: Fn($($ident),*) -> bool>(p: P) -> When<($($ident,)*), P> {
-                When { predicate: p, _marker: PhantomData }
-            }
         }
 
         impl <P, F, T, $($ident: Clone),*> QuickFn<($($ident,)*)> for WhenFn<($($ident,)*), P, F>
@@ -1,8 +1,11 @@
 use generate::GenerateCtx;
-use testable::{IntoTestable, Testable, TestStatus, TestResult};
+use testable::{IntoTestable, Testable, ExhaustiveTestable, TestStatus, TestResult};
 use rose::Rose;
+use shrink_trace::ShrinkTrace;
 
-use std::{self, cmp};
+use std::{self, cmp, thread};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use rand::{self, Rng, StdRng, SeedableRng};
 use log::LogLevel;
 
@@ -27,6 +30,7 @@ pub struct QuickCheck {
     tests: usize,
     max_discard_ratio: usize,
     max_size: usize,
+    shrink_trace: Option<ShrinkTrace>,
     rng: rand::ThreadRng
 }
 
@@ -76,6 +80,7 @@ impl QuickCheck
             tests: 100,
             max_discard_ratio: 10,
             max_size: 100,
+            shrink_trace: None,
             rng: rand::thread_rng()
         }
     }
@@ -87,6 +92,27 @@ impl QuickCheck
         }
     }
 
+    /// Record every candidate tried while shrinking a failure.  The record of the most recent
+    /// shrink is available from `shrink_trace` and is also logged at debug level.
+    pub fn trace_shrinks(self, trace_shrinks: bool) -> Self {
+        QuickCheck {
+            shrink_trace: match trace_shrinks {
+                true => Some(ShrinkTrace::new()),
+                false => None
+            },
+            ..self
+        }
+    }
+
+    /// The record of the most recent shrink, if shrinks are traced and the last run failed.
+    pub fn shrink_trace(&self) -> Option<&ShrinkTrace> {
+        self.shrink_trace.as_ref().filter(|trace| !trace.steps.is_empty())
+    }
+
+    fn reset_shrink_trace(&mut self) {
+        if self.shrink_trace.is_some() { self.shrink_trace = Some(ShrinkTrace::new()); }
+    }
+
     pub fn tests(self, tests: usize) -> Self {
         QuickCheck {
             tests: tests,
@@ -94,6 +120,16 @@ impl QuickCheck
         }
     }
 
+    /// Run test cases on `threads` worker threads.  Only properties that are `Sync` can be
+    /// tested this way; see `ParallelQuickCheck`.
+    pub fn threads(self, threads: usize) -> ParallelQuickCheck {
+        ParallelQuickCheck {
+            quick_check: self,
+            threads: cmp::max(threads, 1),
+            seed: None
+        }
+    }
+
     pub fn quicktest<T: IntoTestable>(&mut self, t: T) -> Result<usize> {
         let _ = ::env_logger::init();
 
@@ -101,6 +137,7 @@ impl QuickCheck
         let max_tests = self.tests * self.max_discard_ratio;
 
         let mut state = QuickCheckState::new();
+        self.reset_shrink_trace();
 
         for _ in 0..max_tests {
             if state.successful_tests >= self.tests { return Ok(state.successful_tests) }
@@ -111,7 +148,7 @@ impl QuickCheck
             let mut ctx = GenerateCtx::new(&mut test_rng, size);
 
             let rose_result = testable.test(&mut ctx);
-            self.log_result(&rose_result.value);
+            QuickCheck::log_result(&rose_result.value);
 
             match rose_result.value.status {
                 TestStatus::Pass => state.test_passed(),
@@ -127,7 +164,7 @@ impl QuickCheck
         state.gave_up_after(max_tests)
     }
 
-    fn log_result(&self, result: &TestResult) {
+    fn log_result(result: &TestResult) {
         let log_level = match result.status {
             TestStatus::Discard => LogLevel::Trace,
             TestStatus::Fail => LogLevel::Info,
@@ -136,12 +173,25 @@ impl QuickCheck
         log!(log_level, "{:?}: {}", result.status, result.input);
     }
 
-    fn shrink_failure<'a>(&self, rose_result: Rose<TestResult>) -> TestResult {
+    fn shrink_failure(&mut self, rose_result: Rose<TestResult>) -> TestResult {
+        let mut trace = self.shrink_trace.take();
+        let minimal_witness = QuickCheck::shrink_failure_traced(rose_result, &mut trace);
+        if let Some(ref trace) = trace {
+            debug!("Shrink trace:\n{}", trace);
+        }
+        self.shrink_trace = trace;
+        minimal_witness
+    }
+
+    fn shrink_failure_traced(rose_result: Rose<TestResult>, trace: &mut Option<ShrinkTrace>) -> TestResult {
+        if let Some(ref mut trace) = *trace { trace.push_step(rose_result.value.clone()); }
+
         for shrunk_result in rose_result.iterator {
             assert!(shrunk_result.value.input != rose_result.value.input);
-            self.log_result(&shrunk_result.value);
+            QuickCheck::log_result(&shrunk_result.value);
+            if let Some(ref mut trace) = *trace { trace.push_candidate(shrunk_result.value.clone()); }
             match shrunk_result.value.status {
-                TestStatus::Fail => return self.shrink_failure(shrunk_result),
+                TestStatus::Fail => return QuickCheck::shrink_failure_traced(shrunk_result, trace),
                 _ => continue
             }
         }
@@ -150,17 +200,18 @@ impl QuickCheck
     }
 
     fn size(&self, state: &QuickCheckState) -> usize {
-        let n = state.successful_tests;
-        let d = state.recently_discarded_tests;
-        let max_size = self.max_size;
+        QuickCheck::size_for(self.tests, self.max_size, state.successful_tests, state.recently_discarded_tests)
+    }
+
+    fn size_for(tests: usize, max_size: usize, n: usize, d: usize) -> usize {
 
         fn round_down_to(value: usize, multiple: usize) -> usize { (value / multiple) * multiple }
 
         let proposed_size = {
-            if (round_down_to(n, max_size) + max_size <= self.tests) || ((self.tests % max_size) == 0) {
+            if (round_down_to(n, max_size) + max_size <= tests) || ((tests % max_size) == 0) {
                 (n % max_size) + d / 10
             } else {
-                ((n % max_size) * max_size) / (self.tests % max_size) + d / 10
+                ((n % max_size) * max_size) / (tests % max_size) + d / 10
             }
         };
 
@@ -168,7 +219,12 @@ impl QuickCheck
     }
 
     pub fn quickcheck<T: IntoTestable>(&mut self, t: T) {
-        match self.quicktest(t) {
+        let result = self.quicktest(t);
+        QuickCheck::report(result)
+    }
+
+    fn report(result: Result<usize>) {
+        match result {
             Ok(ntests) => info!("(Passed {} QuickCheck tests.)", ntests),
             Err(err) => {
                 match err {
@@ -181,5 +237,194 @@ impl QuickCheck
     }
 }
 
+/// A `QuickCheck` runner that distributes test cases over a pool of threads.
+///
+/// The seed and size of each test case are derived from a single master seed and the index of
+/// the case, so a run with a fixed `seed` has the same outcome no matter how many threads are
+/// used.  Unlike the sequential runner, sizes grow with the case index rather than with the number
+/// of passed tests, so discarded cases shift the sizes that later cases are generated at.
+///
+/// Workers stop claiming new cases as soon as any case fails.  Cases already in flight are run
+/// to completion, and the failing case with the lowest index is then re-run and shrunk on the
+/// calling thread, as the `Rose` shrink trees that properties build are not `Send`.  A failure is
+/// only reported if fewer than `tests` cases before it passed, as a single thread would have
+/// stopped before reaching it otherwise.
+pub struct ParallelQuickCheck {
+    quick_check: QuickCheck,
+    threads: usize,
+    seed: Option<usize>
+}
+
+struct ParallelFailure {
+    case: usize,
+    seed: usize,
+    size: usize
+}
+
+impl ParallelQuickCheck
+{
+    pub fn seed(self, seed: usize) -> Self {
+        ParallelQuickCheck {
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    pub fn shrink_trace(&self) -> Option<&ShrinkTrace> {
+        self.quick_check.shrink_trace()
+    }
+
+    fn case_seed(master_seed: usize, case: usize) -> usize {
+        StdRng::from_seed(&[master_seed, case]).gen()
+    }
+
+    pub fn quicktest<T>(&mut self, t: T) -> Result<usize>
+        where T: IntoTestable,
+              T::Testable: Sync
+    {
+        let _ = ::env_logger::init();
+
+        let testable = t.into_testable();
+        let tests = self.quick_check.tests;
+        let max_size = self.quick_check.max_size;
+        let max_tests = tests * self.quick_check.max_discard_ratio;
+        let master_seed = match self.seed {
+            Some(seed) => seed,
+            None => self.quick_check.rng.gen()
+        };
+
+        let next_case = AtomicUsize::new(0);
+        let successful_tests = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let failure: Mutex<Option<ParallelFailure>> = Mutex::new(None);
+        let passed_cases: Mutex<Vec<usize>> = Mutex::new(vec![]);
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                scope.spawn(|| {
+                    while !failed.load(Ordering::SeqCst) && successful_tests.load(Ordering::SeqCst) < tests {
+                        let case = next_case.fetch_add(1, Ordering::SeqCst);
+                        if case >= max_tests { break }
+
+                        let seed = ParallelQuickCheck::case_seed(master_seed, case);
+                        let mut test_rng = StdRng::from_seed(&[seed]);
+                        let size = QuickCheck::size_for(tests, max_size, case, 0);
+                        let mut ctx = GenerateCtx::new(&mut test_rng, size);
+
+                        let rose_result = testable.test(&mut ctx);
+                        QuickCheck::log_result(&rose_result.value);
+
+                        match rose_result.value.status {
+                            TestStatus::Pass => {
+                                passed_cases.lock().unwrap().push(case);
+                                successful_tests.fetch_add(1, Ordering::SeqCst);
+                            },
+                            TestStatus::Discard => (),
+                            TestStatus::Fail => {
+                                failed.store(true, Ordering::SeqCst);
+                                let mut failure = failure.lock().unwrap();
+                                let is_first = failure.as_ref().map_or(true, |f| case < f.case);
+                                if is_first {
+                                    *failure = Some(ParallelFailure { case: case, seed: seed, size: size });
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut state = QuickCheckState::new();
+        self.quick_check.reset_shrink_trace();
+
+        let passed_cases = passed_cases.into_inner().unwrap();
+        let failure = failure.into_inner().unwrap().and_then(|failure| {
+            let passed_before = passed_cases.iter().filter(|&&case| case < failure.case).count();
+            state.successful_tests = passed_before;
+            match passed_before < tests {
+                true => Some(failure),
+                false => None
+            }
+        });
+        if failure.is_none() {
+            state.successful_tests = cmp::min(passed_cases.len(), tests);
+        }
+
+        match failure {
+            Some(ParallelFailure { seed, size, .. }) => {
+                let mut test_rng = StdRng::from_seed(&[seed]);
+                let mut ctx = GenerateCtx::new(&mut test_rng, size);
+                let rose_result = testable.test(&mut ctx);
+
+                info!("Attempting to reduce to a minimal failing case...");
+                let minimal_witness = self.quick_check.shrink_failure(rose_result);
+                state.test_failed(&testable, minimal_witness, seed, size)
+            },
+            None if state.successful_tests >= tests => Ok(state.successful_tests),
+            None => state.gave_up_after(max_tests)
+        }
+    }
+
+    pub fn quickcheck<T>(&mut self, t: T)
+        where T: IntoTestable,
+              T::Testable: Sync
+    {
+        let result = self.quicktest(t);
+        QuickCheck::report(result)
+    }
+}
+
+/// A runner that tests a property against every enumerated input up to a depth, instead of
+/// against random inputs.  Only properties whose arguments implement `Enumerate` can be tested
+/// this way.
+///
+/// Inputs are tried in enumeration order, smallest first, so the first failing input is already
+/// minimal and is reported as-is, without shrinking.  The arguments of a property are ordered by
+/// their total depth.  Every input is tested, so there is no limit on the number of tests or
+/// discards.  As no randomness is involved, a `Failure` reports a `seed` of zero and the
+/// enumeration depth as its `size`.
+pub struct ExhaustiveQuickCheck {
+    depth: usize
+}
+
+impl ExhaustiveQuickCheck
+{
+    pub fn new(depth: usize) -> Self {
+        ExhaustiveQuickCheck { depth: depth }
+    }
+
+    pub fn quicktest<T>(&mut self, t: T) -> Result<usize>
+        where T: IntoTestable,
+              T::Testable: ExhaustiveTestable
+    {
+        let _ = ::env_logger::init();
+
+        let testable = t.into_testable();
+        let mut state = QuickCheckState::new();
+
+        let failure = testable.test_exhaustive(self.depth)
+            .inspect(QuickCheck::log_result)
+            .filter_map(|result| match result.status {
+                TestStatus::Pass => { state.test_passed(); None },
+                TestStatus::Discard => { state.test_discarded(); None },
+                TestStatus::Fail => Some(result)
+            })
+            .next();
+
+        match failure {
+            Some(result) => state.test_failed(&testable, result, 0, self.depth),
+            None => Ok(state.successful_tests)
+        }
+    }
+
+    pub fn quickcheck<T>(&mut self, t: T)
+        where T: IntoTestable,
+              T::Testable: ExhaustiveTestable
+    {
+        let result = self.quicktest(t);
+        QuickCheck::report(result)
+    }
+}
+
 pub fn quicktest<T: IntoTestable>(t: T) -> Result<usize> { QuickCheck::new().quicktest(t) }
 pub fn quickcheck<T: IntoTestable>(t: T) { QuickCheck::new().quickcheck(t) }
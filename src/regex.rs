@@ -0,0 +1,436 @@
+use generate::{Generator, GenerateCtx};
+use shrink::{Shrink, FromIteratorShrinker};
+
+use std::cmp;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::sync::Arc;
+
+use rand;
+
+/// A regular expression that drives string generation.
+///
+/// The supported syntax is a practical subset: literals and escapes, `.`, character classes
+/// such as `[a-z_]` and `[^0-9]`, the classes `\d`, `\w` and `\s` and their negations, groups,
+/// alternation, and the repetitions `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`.  Patterns always
+/// match the whole string, so the anchors `^` and `$` are accepted and ignored.  `.` and negated
+/// classes draw from printable ASCII.
+#[derive(Clone, Debug)]
+pub struct Regex {
+    node: Arc<Node>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: &'static str
+}
+
+#[derive(Debug)]
+enum Node {
+    Empty,
+    Literal(char),
+    Class(Vec<(char, char)>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat(Box<Node>, usize, Option<usize>)
+}
+
+const PRINTABLE: (char, char) = (' ', '~');
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: usize
+}
+
+impl <'a> Parser<'a> {
+    fn error<T>(&self, message: &'static str) -> Result<T, ParseError> {
+        Err(ParseError { position: self.position, message: message })
+    }
+
+    fn next(&mut self) -> Option<char> {
+        self.position += 1;
+        self.chars.next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.chars.peek() == Some(&c) { self.next(); true } else { false }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, ParseError> {
+        let mut alternatives = vec![self.parse_concat()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_concat()?);
+        }
+        Ok(if alternatives.len() == 1 { alternatives.pop().unwrap() } else { Node::Alternate(alternatives) })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, ParseError> {
+        let mut nodes = vec![];
+        loop {
+            match self.chars.peek().cloned() {
+                None | Some('|') | Some(')') => break,
+                Some(_) => {
+                    let atom = self.parse_atom()?;
+                    nodes.push(self.parse_repeat(atom)?);
+                }
+            }
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap(),
+            _ => Node::Concat(nodes)
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ParseError> {
+        match self.next() {
+            Some('(') => {
+                if self.eat('?') && !self.eat(':') { return self.error("Only non-capturing groups (?:...) are supported") }
+                let node = self.parse_alternation()?;
+                if !self.eat(')') { return self.error("Unclosed group") }
+                Ok(node)
+            },
+            Some('[') => self.parse_class(),
+            Some('.') => Ok(Node::Class(vec![PRINTABLE])),
+            Some('^') | Some('$') => Ok(Node::Empty),
+            Some('\\') => self.parse_escape().map(|ranges| match ranges.len() {
+                1 if ranges[0].0 == ranges[0].1 => Node::Literal(ranges[0].0),
+                _ => Node::Class(ranges)
+            }),
+            Some('*') | Some('+') | Some('?') | Some('{') => self.error("Repetition without anything to repeat"),
+            Some(c) => Ok(Node::Literal(c)),
+            None => self.error("Unexpected end of pattern")
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<Vec<(char, char)>, ParseError> {
+        Ok(match self.next() {
+            Some('d') => vec![('0', '9')],
+            Some('w') => vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+            Some('s') => vec![('\t', '\n'), (' ', ' ')],
+            Some('D') => negate(&[('0', '9')]),
+            Some('W') => negate(&[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')]),
+            Some('S') => negate(&[('\t', '\n'), (' ', ' ')]),
+            Some('n') => vec![('\n', '\n')],
+            Some('t') => vec![('\t', '\t')],
+            Some('r') => vec![('\r', '\r')],
+            Some(c) => vec![(c, c)],
+            None => return self.error("Unexpected end of pattern after \\")
+        })
+    }
+
+    fn parse_class(&mut self) -> Result<Node, ParseError> {
+        let negated = self.eat('^');
+        let mut ranges = vec![];
+        let mut first = true;
+        loop {
+            let start = match self.next() {
+                Some(']') if !first => break,
+                Some('\\') => {
+                    let escaped = self.parse_escape()?;
+                    if escaped.len() != 1 || escaped[0].0 != escaped[0].1 {
+                        ranges.extend(escaped);
+                        first = false;
+                        continue;
+                    }
+                    escaped[0].0
+                },
+                Some(c) => c,
+                None => return self.error("Unclosed character class")
+            };
+            first = false;
+            if self.chars.peek() == Some(&'-') {
+                self.next();
+                match self.chars.peek().cloned() {
+                    Some(']') => { ranges.push((start, start)); ranges.push(('-', '-')); },
+                    Some(end) => {
+                        self.next();
+                        if end < start { return self.error("Character class range is out of order") }
+                        ranges.push((start, end));
+                    },
+                    None => return self.error("Unclosed character class")
+                }
+            } else {
+                ranges.push((start, start));
+            }
+        }
+        let ranges = if negated { negate(&ranges) } else { ranges };
+        if ranges.is_empty() { return self.error("Character class matches nothing") }
+        Ok(Node::Class(ranges))
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let mut number = None;
+        while let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            self.next();
+            number = Some(number.unwrap_or(0) * 10 + digit as usize);
+        }
+        number
+    }
+
+    fn parse_repeat(&mut self, atom: Node) -> Result<Node, ParseError> {
+        let (min, max) = match self.chars.peek().cloned() {
+            Some('*') => { self.next(); (0, None) },
+            Some('+') => { self.next(); (1, None) },
+            Some('?') => { self.next(); (0, Some(1)) },
+            Some('{') => {
+                self.next();
+                let min = match self.parse_number() {
+                    Some(min) => min,
+                    None => return self.error("Expected a number in {n,m}")
+                };
+                let max = if self.eat(',') { self.parse_number() } else { Some(min) };
+                if !self.eat('}') { return self.error("Unclosed {n,m}") }
+                if max.map_or(false, |max| max < min) { return self.error("Repetition range is out of order") }
+                (min, max)
+            },
+            _ => return Ok(atom)
+        };
+        self.parse_repeat(Node::Repeat(Box::new(atom), min, max))
+    }
+}
+
+/// The printable ASCII characters that aren't in `ranges`.
+fn negate(ranges: &[(char, char)]) -> Vec<(char, char)> {
+    let mut negated = vec![];
+    let mut start = PRINTABLE.0 as u32;
+    let mut sorted = ranges.to_vec();
+    sorted.sort();
+    for &(lo, hi) in sorted.iter() {
+        let (lo, hi) = (lo as u32, hi as u32);
+        if lo > start && start <= PRINTABLE.1 as u32 {
+            negated.push((start, cmp::min(lo - 1, PRINTABLE.1 as u32)));
+        }
+        start = cmp::max(start, hi + 1);
+    }
+    if start <= PRINTABLE.1 as u32 {
+        negated.push((start, PRINTABLE.1 as u32));
+    }
+    negated.into_iter()
+        .map(|(lo, hi)| (::std::char::from_u32(lo).unwrap(), ::std::char::from_u32(hi).unwrap()))
+        .collect()
+}
+
+impl Node {
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>, out: &mut String) {
+        match *self {
+            Node::Empty => (),
+            Node::Literal(c) => out.push(c),
+            Node::Class(ref ranges) => {
+                let total = ranges.iter().map(|&(lo, hi)| hi as u32 - lo as u32 + 1).sum::<u32>();
+                let mut idx = ctx.rng.gen_range(0, total);
+                for &(lo, hi) in ranges.iter() {
+                    let len = hi as u32 - lo as u32 + 1;
+                    if idx < len {
+                        // Ranges may straddle the surrogate code points, so fall back to `lo`.
+                        out.push(::std::char::from_u32(lo as u32 + idx).unwrap_or(lo));
+                        return;
+                    }
+                    idx -= len;
+                }
+            },
+            Node::Concat(ref nodes) => for node in nodes.iter() { node.generate(ctx, out) },
+            Node::Alternate(ref nodes) => {
+                let idx = ctx.rng.gen_range(0, nodes.len());
+                nodes[idx].generate(ctx, out)
+            },
+            Node::Repeat(ref node, min, max) => {
+                let upper = cmp::min(max.unwrap_or(<usize>::max_value()), min.saturating_add(ctx.size));
+                let count = ctx.rng.gen_range(min, upper.saturating_add(1));
+                for _ in 0..count { node.generate(ctx, out) }
+            }
+        }
+    }
+
+    fn matches(&self, s: &[char], i: usize, k: &mut FnMut(usize) -> bool) -> bool {
+        match *self {
+            Node::Empty => k(i),
+            Node::Literal(c) => i < s.len() && s[i] == c && k(i + 1),
+            Node::Class(ref ranges) =>
+                i < s.len() && ranges.iter().any(|&(lo, hi)| lo <= s[i] && s[i] <= hi) && k(i + 1),
+            Node::Concat(ref nodes) => Node::matches_sequence(nodes, s, i, k),
+            Node::Alternate(ref nodes) => nodes.iter().any(|node| node.matches(s, i, k)),
+            Node::Repeat(ref node, min, max) => Node::matches_repeat(node, min, max, 0, s, i, k)
+        }
+    }
+
+    fn matches_sequence(nodes: &[Node], s: &[char], i: usize, k: &mut FnMut(usize) -> bool) -> bool {
+        match nodes.split_first() {
+            None => k(i),
+            Some((first, rest)) => first.matches(s, i, &mut |j| Node::matches_sequence(rest, s, j, k))
+        }
+    }
+
+    fn matches_repeat(node: &Node, min: usize, max: Option<usize>, count: usize,
+                      s: &[char], i: usize, k: &mut FnMut(usize) -> bool) -> bool {
+        if count >= min && k(i) { return true }
+        if max.map_or(false, |max| count >= max) { return false }
+        // Past the minimum, only repetitions that consume input can lead to a new match.
+        node.matches(s, i, &mut |j| (j > i || count < min) && Node::matches_repeat(node, min, max, count + 1, s, j, k))
+    }
+
+    /// The simplest characters the pattern could use: the first character of every class and
+    /// every literal.
+    fn simplest_chars(&self, chars: &mut Vec<char>) {
+        match *self {
+            Node::Empty => (),
+            Node::Literal(c) => chars.push(c),
+            Node::Class(ref ranges) => chars.extend(ranges.iter().map(|&(lo, _)| lo)),
+            Node::Concat(ref nodes) | Node::Alternate(ref nodes) =>
+                for node in nodes.iter() { node.simplest_chars(chars) },
+            Node::Repeat(ref node, _, _) => node.simplest_chars(chars)
+        }
+    }
+}
+
+impl Regex {
+    pub fn parse(pattern: &str) -> Result<Regex, ParseError> {
+        let mut parser = Parser { chars: pattern.chars().peekable(), position: 0 };
+        let node = parser.parse_alternation()?;
+        match parser.chars.peek() {
+            Some(_) => parser.error("Unmatched )"),
+            None => Ok(Regex { node: Arc::new(node) })
+        }
+    }
+
+    pub fn is_match(&self, s: &str) -> bool {
+        let chars = s.chars().collect::<Vec<_>>();
+        self.node.matches(&chars, 0, &mut |j| j == chars.len())
+    }
+
+    pub fn generator(&self) -> RegexGenerator {
+        RegexGenerator { regex: self.clone() }
+    }
+
+    pub fn shrinker(&self) -> RegexShrinker {
+        let mut simplest = vec![];
+        self.node.simplest_chars(&mut simplest);
+        simplest.sort();
+        simplest.dedup();
+        RegexShrinker {
+            regex: self.clone(),
+            chars: FromIteratorShrinker::new(SimplerCharShrinker { simplest: Arc::new(simplest) })
+        }
+    }
+}
+
+/// Generates strings that match a `Regex`.  Unbounded repetitions repeat at most as many times
+/// as the size of the `GenerateCtx`, on top of their minimum.
+#[derive(Clone)]
+pub struct RegexGenerator {
+    regex: Regex
+}
+
+impl Generator for RegexGenerator {
+    type Output = String;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> String {
+        let mut out = String::new();
+        self.regex.node.generate(ctx, &mut out);
+        out
+    }
+}
+
+#[derive(Clone)]
+struct SimplerCharShrinker {
+    simplest: Arc<Vec<char>>
+}
+
+impl Shrink for SimplerCharShrinker {
+    type Item = char;
+    type Iterator = ::std::vec::IntoIter<char>;
+
+    fn shrink(&self, c: &char) -> Self::Iterator {
+        self.simplest.iter().cloned().filter(|s| s < c).collect::<Vec<_>>().into_iter()
+    }
+}
+
+/// Shrinks strings within the language of a `Regex`, by removing characters and replacing them
+/// by the simplest characters the pattern uses.  Only candidates that still match are produced.
+#[derive(Clone)]
+pub struct RegexShrinker {
+    regex: Regex,
+    chars: FromIteratorShrinker<Vec<char>, SimplerCharShrinker>
+}
+
+impl Shrink for RegexShrinker {
+    type Item = String;
+    type Iterator = Box<Iterator<Item=String>>;
+
+    fn shrink(&self, s: &String) -> Self::Iterator {
+        let regex = self.regex.clone();
+        Box::new(
+            self.chars.shrink(&s.chars().collect())
+                .map(|chars| chars.into_iter().collect::<String>())
+                .filter(move |candidate| regex.is_match(candidate))
+        )
+    }
+}
+
+/// A generator and matching shrinker for strings matching `pattern`.
+///
+/// Panics if `pattern` isn't a supported regular expression; use `Regex::parse` to handle the
+/// error instead.
+pub fn regex(pattern: &str) -> (RegexGenerator, RegexShrinker) {
+    match Regex::parse(pattern) {
+        Ok(regex) => (regex.generator(), regex.shrinker()),
+        Err(err) => panic!("Invalid regular expression {:?}: {:?}", pattern, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use generate::{Generator, GenerateCtx};
+    use shrink::Shrink;
+    use rand;
+
+    const PATTERNS: &'static [&'static str] = &[
+        "[a-z_][a-z0-9_]*",
+        "^\\d{4}-\\d{2}-\\d{2}$",
+        "[a-z]+@[a-z]+\\.(com|org|net)",
+        "(?:ab|c)*d?[^a-z]{1,3}",
+        "\\w\\W\\s\\S."
+    ];
+
+    #[test]
+    fn generated_strings_match() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 20);
+        for pattern in PATTERNS.iter() {
+            let regex = Regex::parse(pattern).unwrap();
+            for _ in 0..100 {
+                let s = regex.generator().generate(&mut ctx);
+                assert!(regex.is_match(&s), "{:?} doesn't match {:?}", s, pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn is_match() {
+        let regex = Regex::parse("a(b|cd)*e{2,3}").unwrap();
+        assert!(regex.is_match("aee"));
+        assert!(regex.is_match("abcdbeee"));
+        assert!(!regex.is_match("abcee_"));
+        assert!(!regex.is_match("aeeee"));
+        assert!(Regex::parse("(a*)*b").unwrap().is_match("aaab"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Regex::parse("a(b").unwrap_err().message, "Unclosed group");
+        assert_eq!(Regex::parse("a)").unwrap_err().position, 1);
+        assert_eq!(Regex::parse("[z-a]").unwrap_err().message, "Character class range is out of order");
+        assert!(Regex::parse("*a").is_err());
+    }
+
+    #[test]
+    fn shrinks_stay_in_language() {
+        let regex = Regex::parse("[a-z]+@[a-z]+\\.(com|org)").unwrap();
+        let shrinks = regex.shrinker().shrink(&"xy@zz.org".to_string()).collect::<Vec<_>>();
+        assert!(shrinks.iter().all(|s| regex.is_match(s)));
+        assert!(shrinks.contains(&"y@zz.org".to_string()));
+        assert!(shrinks.contains(&"xy@zz.com".to_string()) || shrinks.contains(&"ay@zz.org".to_string()));
+    }
+}
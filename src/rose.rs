@@ -1,5 +1,7 @@
-use std::iter;
+use std::cell::RefCell;
+use std::iter::{self, Fuse};
 use std::rc::Rc;
+use std::sync::Arc;
 
 pub struct Rose<T: 'static>
 {
@@ -25,6 +27,116 @@ impl <T: 'static> Rose<T>
             iterator: Box::new(iter::empty())
         }
     }
+
+    /// Shrink the outer tree first, and then the tree produced by `f` from the outer value.
+    pub fn flat_map<F: Fn(T) -> Rose<R> + 'static, R>(self, f: F) -> Rose<R> {
+        self.flat_map_rc(Rc::new(f))
+    }
+
+    pub fn flat_map_rc<F: Fn(T) -> Rose<R> + 'static, R>(self, f: Rc<F>) -> Rose<R> {
+        let outer = self.iterator;
+        let inner = (&f)(self.value);
+        Rose {
+            value: inner.value,
+            iterator: Box::new(
+                outer.scan(f, |f, r| Some(r.flat_map_rc(f.clone())))
+                    .chain(inner.iterator)
+            )
+        }
+    }
+
+    /// Prune every subtree whose root doesn't satisfy `predicate`.  The root itself is kept.
+    pub fn filter<P: Fn(&T) -> bool + 'static>(self, predicate: P) -> Rose<T> {
+        self.filter_rc(Rc::new(predicate))
+    }
+
+    pub fn filter_rc<P: Fn(&T) -> bool + 'static>(self, predicate: Rc<P>) -> Rose<T> {
+        let p = predicate.clone();
+        Rose {
+            value: self.value,
+            iterator: Box::new(
+                self.iterator
+                    .filter(move |r| (&p)(&r.value))
+                    .scan(predicate, |p, r| Some(r.filter_rc(p.clone())))
+            )
+        }
+    }
+
+    /// Cut the tree off below `depth` levels of shrinking.
+    pub fn take_depth(self, depth: usize) -> Rose<T> {
+        match depth {
+            0 => Rose::single(self.value),
+            _ => Rose {
+                value: self.value,
+                iterator: Box::new(self.iterator.map(move |r| r.take_depth(depth - 1)))
+            }
+        }
+    }
+
+    /// Pair up the values of two trees.  The left tree is shrunk first while the right value is
+    /// held fixed, and then the right tree is shrunk while the left value is held fixed.  Each
+    /// subtree is produced once and shared by every pair it ends up in, so the values are cloned
+    /// but the trees are not.
+    pub fn zip<U: Clone + 'static>(self, other: Rose<U>) -> Rose<(T, U)>
+        where T: Clone
+    {
+        zip_shared(SharedRose::new(self), SharedRose::new(other))
+    }
+}
+
+fn zip_shared<T: Clone + 'static, U: Clone + 'static>(left: SharedRose<T>, right: SharedRose<U>) -> Rose<(T, U)> {
+    let this = left.clone();
+    let that = right.clone();
+    Rose {
+        value: (left.value().clone(), right.value().clone()),
+        iterator: Box::new(
+            left.iterator().map(move |l| zip_shared(l, that.clone()))
+                .chain(right.iterator().map(move |r| zip_shared(this.clone(), r)))
+        )
+    }
+}
+
+/// A `Rose` whose subtrees are taken from its iterator on first use and then kept, so that clones
+/// of it can all walk the same subtrees.
+struct SharedRose<T: 'static>(Rc<SharedNode<T>>);
+
+struct SharedNode<T: 'static> {
+    value: T,
+    iterator: RefCell<Fuse<Box<Iterator<Item=Rose<T>>>>>,
+    children: RefCell<Vec<SharedRose<T>>>
+}
+
+impl <T: 'static> Clone for SharedRose<T> {
+    fn clone(&self) -> Self {
+        SharedRose(self.0.clone())
+    }
+}
+
+impl <T: 'static> SharedRose<T> {
+    fn new(rose: Rose<T>) -> SharedRose<T> {
+        SharedRose(Rc::new(SharedNode {
+            value: rose.value,
+            iterator: RefCell::new(rose.iterator.fuse()),
+            children: RefCell::new(vec![])
+        }))
+    }
+
+    fn value(&self) -> &T { &self.0.value }
+
+    fn child(&self, n: usize) -> Option<SharedRose<T>> {
+        let mut children = self.0.children.borrow_mut();
+        while children.len() <= n {
+            match self.0.iterator.borrow_mut().next() {
+                Some(rose) => children.push(SharedRose::new(rose)),
+                None => return None
+            }
+        }
+        Some(children[n].clone())
+    }
+
+    fn iterator(&self) -> Box<Iterator<Item=SharedRose<T>>> {
+        Box::new((0..).scan(self.clone(), |this, n| this.child(n)))
+    }
 }
 
 pub trait RoseTrait<T: 'static> {
@@ -38,6 +150,12 @@ pub trait RoseTraitMap<T: 'static> {
     fn map_rc<F: Fn(T) -> R + 'static, R>(self, f: Rc<F>) -> Rose<R>;
     fn scan<S: 'static, F: Fn(&S, T) -> R + 'static, R>(self, state: S, f: F) -> Rose<R>;
     fn scan_rc<S: 'static, F: Fn(&S, T) -> R + 'static, R>(self, state: Rc<S>, f: Rc<F>) -> Rose<R>;
+    fn into_rose(self) -> Rose<T>;
+    fn zip<U: Clone + 'static, OTHER: RoseTrait<U> + 'static>(self, other: OTHER) -> Rose<(T, U)>
+        where T: Clone;
+    fn flat_map<F: Fn(T) -> INNER + 'static, INNER: RoseTrait<R> + 'static, R>(self, f: F) -> Rose<R>;
+    fn filter<P: Fn(&T) -> bool + 'static>(self, predicate: P) -> Rose<T>;
+    fn take_depth(self, depth: usize) -> Rose<T>;
 }
 
 impl <T: 'static, ROSE: RoseTrait<T> + 'static> RoseTraitMap<T> for ROSE {
@@ -66,6 +184,28 @@ impl <T: 'static, ROSE: RoseTrait<T> + 'static> RoseTraitMap<T> for ROSE {
             )))
         }
     }
+
+    fn into_rose(self) -> Rose<T> {
+        self.map(|t| t)
+    }
+
+    fn zip<U: Clone + 'static, OTHER: RoseTrait<U> + 'static>(self, other: OTHER) -> Rose<(T, U)>
+        where T: Clone
+    {
+        self.into_rose().zip(other.into_rose())
+    }
+
+    fn flat_map<F: Fn(T) -> INNER + 'static, INNER: RoseTrait<R> + 'static, R>(self, f: F) -> Rose<R> {
+        self.into_rose().flat_map(move |t| f(t).into_rose())
+    }
+
+    fn filter<P: Fn(&T) -> bool + 'static>(self, predicate: P) -> Rose<T> {
+        self.into_rose().filter(predicate)
+    }
+
+    fn take_depth(self, depth: usize) -> Rose<T> {
+        self.into_rose().take_depth(depth)
+    }
 }
 
 pub struct GeneratedRose<T: 'static, F>
@@ -74,6 +214,12 @@ pub struct GeneratedRose<T: 'static, F>
     generator: Rc<F>
 }
 
+impl <T: Clone + 'static, F> Clone for GeneratedRose<T, F> {
+    fn clone(&self) -> Self {
+        GeneratedRose { value: self.value.clone(), generator: self.generator.clone() }
+    }
+}
+
 impl <T: 'static, F> GeneratedRose<T, F>
     where F: Fn(&T) -> Box<Iterator<Item=T>> + 'static
 {
@@ -105,6 +251,12 @@ pub struct GenerateWithRose<T: 'static, S, F>
     generator: Rc<F>
 }
 
+impl <T: Clone + 'static, S, F> Clone for GenerateWithRose<T, S, F> {
+    fn clone(&self) -> Self {
+        GenerateWithRose { value: self.value.clone(), state: self.state.clone(), generator: self.generator.clone() }
+    }
+}
+
 impl <T: 'static, S: 'static, F> GenerateWithRose<T, S, F>
     where F: Fn(&S, &T) -> Box<Iterator<Item=T>> + 'static
 {
@@ -131,3 +283,239 @@ impl <T: 'static, S: 'static, F> RoseTrait<T> for GenerateWithRose<T, S, F>
         )
     }
 }
+
+/// A `Rose` that can be sent between threads, so that a shrink tree can be handed to another
+/// thread to explore.  `GeneratedSendRose` and `GenerateWithSendRose` share their generators
+/// through `Arc` where their `Rose` counterparts use `Rc`.
+pub struct SendRose<T: Send + 'static>
+{
+    pub value: T,
+    pub iterator: Box<Iterator<Item=SendRose<T>> + Send>
+}
+
+impl <T: Send + 'static> SendRose<T>
+{
+    pub fn new<I>(value: T, iterator: I) -> SendRose<T>
+        where I: Iterator<Item=SendRose<T>> + Send + 'static
+    {
+        SendRose {
+            value: value,
+            iterator: Box::new(iterator)
+        }
+    }
+
+    pub fn single(value: T) -> SendRose<T>
+    {
+        SendRose {
+            value: value,
+            iterator: Box::new(iter::empty())
+        }
+    }
+
+    pub fn into_rose(self) -> Rose<T>
+    {
+        Rose {
+            value: self.value,
+            iterator: Box::new(self.iterator.map(SendRose::into_rose))
+        }
+    }
+}
+
+pub trait SendRoseTrait<T: Send + 'static>: Send {
+    fn into_value(self) -> T;
+    fn value(&self) -> &T;
+    fn iterator(&self) -> Box<Iterator<Item=Self> + Send>;
+}
+
+pub trait SendRoseTraitMap<T: Send + 'static> {
+    fn map<F: Fn(T) -> R + Send + Sync + 'static, R: Send>(self, f: F) -> SendRose<R>;
+    fn map_arc<F: Fn(T) -> R + Send + Sync + 'static, R: Send>(self, f: Arc<F>) -> SendRose<R>;
+    fn scan<S: Send + Sync + 'static, F: Fn(&S, T) -> R + Send + Sync + 'static, R: Send>(self, state: S, f: F) -> SendRose<R>;
+    fn scan_arc<S: Send + Sync + 'static, F: Fn(&S, T) -> R + Send + Sync + 'static, R: Send>(self, state: Arc<S>, f: Arc<F>) -> SendRose<R>;
+}
+
+impl <T: Send + 'static, ROSE: SendRoseTrait<T> + 'static> SendRoseTraitMap<T> for ROSE {
+    fn map<F: Fn(T) -> R + Send + Sync + 'static, R: Send>(self, f: F) -> SendRose<R> {
+        self.map_arc(Arc::new(f))
+    }
+
+    fn map_arc<F: Fn(T) -> R + Send + Sync + 'static, R: Send>(self, f: Arc<F>) -> SendRose<R> {
+        let iterator = self.iterator();
+        SendRose {
+            value: (&f)(self.into_value()),
+            iterator: Box::new(iterator.scan(f, |f, r| Some(r.map_arc(f.clone()))))
+        }
+    }
+
+    fn scan<S: Send + Sync + 'static, F: Fn(&S, T) -> R + Send + Sync + 'static, R: Send>(self, state: S, f: F) -> SendRose<R> {
+        self.scan_arc(Arc::new(state), Arc::new(f))
+    }
+
+    fn scan_arc<S: Send + Sync + 'static, F: Fn(&S, T) -> R + Send + Sync + 'static, R: Send>(self, state: Arc<S>, f: Arc<F>) -> SendRose<R> {
+        let iterator = self.iterator();
+        SendRose {
+            value: (&f)(&state, self.into_value()),
+            iterator: Box::new(iterator.scan((state, f), |&mut (ref mut state, ref mut f), r|
+                Some(r.scan_arc(state.clone(), f.clone())
+            )))
+        }
+    }
+}
+
+pub struct GeneratedSendRose<T: Send + 'static, F>
+{
+    value: T,
+    generator: Arc<F>
+}
+
+impl <T: Send + 'static, F> GeneratedSendRose<T, F>
+    where F: Fn(&T) -> Box<Iterator<Item=T> + Send> + Send + Sync + 'static
+{
+    pub fn new(value: T, generator: F) -> GeneratedSendRose<T, F> {
+        GeneratedSendRose {
+            value: value,
+            generator: Arc::new(generator)
+        }
+    }
+}
+
+impl <T: Send + 'static, F> SendRoseTrait<T> for GeneratedSendRose<T, F>
+    where F: Fn(&T) -> Box<Iterator<Item=T> + Send> + Send + Sync + 'static
+{
+    fn value(&self) -> &T { &self.value }
+    fn into_value(self) -> T { self.value }
+    fn iterator(&self) -> Box<Iterator<Item=Self> + Send> {
+        Box::new(
+            (&self.generator)(self.value()).scan(self.generator.clone(), |f, t|
+                Some(GeneratedSendRose { value: t, generator: f.clone() })
+        ))
+    }
+}
+
+pub struct GenerateWithSendRose<T: Send + 'static, S, F>
+{
+    value: T,
+    state: Arc<S>,
+    generator: Arc<F>
+}
+
+impl <T: Send + 'static, S: Send + Sync + 'static, F> GenerateWithSendRose<T, S, F>
+    where F: Fn(&S, &T) -> Box<Iterator<Item=T> + Send> + Send + Sync + 'static
+{
+    pub fn new(value: T, state: S, generator: F) -> GenerateWithSendRose<T, S, F> {
+        GenerateWithSendRose {
+            value: value,
+            state: Arc::new(state),
+            generator: Arc::new(generator)
+        }
+    }
+}
+
+impl <T: Send + 'static, S: Send + Sync + 'static, F> SendRoseTrait<T> for GenerateWithSendRose<T, S, F>
+    where F: Fn(&S, &T) -> Box<Iterator<Item=T> + Send> + Send + Sync + 'static
+{
+    fn value(&self) -> &T { &self.value }
+    fn into_value(self) -> T { self.value }
+    fn iterator(&self) -> Box<Iterator<Item=Self> + Send> {
+        Box::new(
+            (&self.generator)(&self.state, self.value())
+                .scan((self.state.clone(), self.generator.clone()), |&mut (ref mut s, ref mut f), t|
+                    Some(GenerateWithSendRose { value: t, state: s.clone(), generator: f.clone()})
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    fn up_to_one(n: &usize) -> Box<Iterator<Item=usize> + Send> {
+        Box::new((0..*n).filter(|x| *x <= 1))
+    }
+
+    fn countdown(n: &usize) -> Box<Iterator<Item=usize>> {
+        Box::new((0..*n).rev())
+    }
+
+    fn values<T: Clone>(rose: Rose<T>) -> Vec<T> {
+        rose.iterator.map(|r| r.value).collect()
+    }
+
+    #[test]
+    fn zip_shrinks_left_then_right() {
+        let rose = GeneratedRose::new(2usize, countdown).zip(GeneratedRose::new(1usize, countdown));
+        assert_eq!(rose.value, (2, 1));
+        assert_eq!(values(rose), vec![(1, 1), (0, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn zip_composed_trees() {
+        let rose = GeneratedRose::new(2usize, countdown).map(|n| n * 10)
+            .zip(GeneratedRose::new(1usize, countdown).map(|n| n + 100));
+        assert_eq!(rose.value, (20, 101));
+        let grandchildren = rose.iterator
+            .map(|r| (r.value, values(r)))
+            .collect::<Vec<_>>();
+        assert_eq!(grandchildren, vec![
+            ((10, 101), vec![(0, 101), (10, 100)]),
+            ((0, 101), vec![(0, 100)]),
+            ((20, 100), vec![(10, 100), (0, 100)])
+        ]);
+    }
+
+    #[test]
+    fn flat_map_shrinks_outer_then_inner() {
+        let rose = GeneratedRose::new(2usize, countdown)
+            .flat_map(|n| GeneratedRose::new(n * 10, countdown));
+        assert_eq!(rose.value, 20);
+        let children = values(rose);
+        assert_eq!(&children[..2], &[10, 0]);
+        assert_eq!(children.len(), 2 + 20);
+    }
+
+    #[test]
+    fn filter_prunes_subtrees() {
+        let rose = GeneratedRose::new(5usize, countdown).filter(|n| n % 2 == 1);
+        assert_eq!(rose.value, 5);
+        let grandchildren = rose.iterator
+            .map(|r| (r.value, values(r)))
+            .collect::<Vec<_>>();
+        assert_eq!(grandchildren, vec![(3, vec![1]), (1, vec![])]);
+    }
+
+    #[test]
+    fn take_depth_limits_depth() {
+        let mut rose = GeneratedRose::new(3usize, countdown).take_depth(1);
+        assert!(rose.iterator.all(|r| r.iterator.count() == 0));
+    }
+
+    #[test]
+    fn send_rose_crosses_threads() {
+        let rose = GeneratedSendRose::new(2usize, up_to_one).map(|n| n * 10);
+        let values = thread::spawn(move || {
+            rose.iterator.map(|r| r.value).collect::<Vec<_>>()
+        }).join().unwrap();
+        assert_eq!(values, vec![0, 10]);
+    }
+
+    #[test]
+    fn send_rose_scan_uses_state() {
+        let rose = GenerateWithSendRose::new(3usize, 1usize, |step, n| Box::new((0..*n).step_by(*step)) as Box<Iterator<Item=usize> + Send>)
+            .scan(100usize, |offset, n| offset + n);
+        assert_eq!(rose.value, 103);
+        let children = rose.iterator.map(|r| r.value).collect::<Vec<_>>();
+        assert_eq!(children, vec![100, 101, 102]);
+    }
+
+    #[test]
+    fn send_rose_into_rose() {
+        let rose = GeneratedSendRose::new(2usize, up_to_one).map(|n| n).into_rose();
+        let grandchildren = rose.iterator
+            .map(|r| r.iterator.map(|r| r.value).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(grandchildren, vec![vec![], vec![0]]);
+    }
+}
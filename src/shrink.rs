@@ -1,11 +1,53 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
+use std::ops::{Range, RangeInclusive};
 use std::iter::{self, FromIterator, IntoIterator};
+use std::sync::Arc;
+
+use num::{Float, Signed, Zero};
+use num::bigint::{BigInt, BigUint};
 
 pub trait Shrink: Clone {
     type Item;
     type Iterator: Iterator<Item=Self::Item>;
 
     fn shrink(&self, value: &Self::Item) -> Self::Iterator;
+
+    /// Shrinks a `T` through an isomorphism: `from` converts it to this shrinker's item and `to`
+    /// converts each candidate back.
+    fn map<F, G, T>(self, to: F, from: G) -> MappedShrinker<Self, F, G>
+        where Self: Sized,
+              F: Fn(Self::Item) -> T,
+              G: Fn(&T) -> Self::Item
+    {
+        MappedShrinker::new(self, to, from)
+    }
+
+    /// Drops candidates that don't satisfy `predicate`, such as those that break an invariant.
+    fn filter<P>(self, predicate: P) -> FilteredShrinker<Self, P>
+        where Self: Sized,
+              P: Fn(&Self::Item) -> bool
+    {
+        FilteredShrinker { shrinker: self, predicate: Arc::new(predicate) }
+    }
+
+    /// Tries the candidates of `other` after this shrinker's own.
+    fn chain<S>(self, other: S) -> ChainedShrinker<Self, S>
+        where Self: Sized,
+              S: Shrink<Item=Self::Item>
+    {
+        ChainedShrinker { first: self, second: other }
+    }
+
+    /// Tries at most `n` candidates at each step.
+    fn limit(self, n: usize) -> LimitedShrinker<Self>
+        where Self: Sized
+    {
+        LimitedShrinker { shrinker: self, limit: n }
+    }
 }
 
 pub struct Empty<T>(PhantomData<T>);
@@ -88,16 +130,34 @@ macro_rules! tuple_impls {
 
 macro_tuples_impl!{tuple_impls}
 
+/// `n / 2`, `n / 4`, ... down to the last non-zero value.  Subtracting these from `n` moves ever
+/// closer to it, so that even the extremes of a type shrink in a logarithmic number of steps.
+macro_rules! halvings {
+    ($n:expr) => {
+        iter::successors(Some($n / 2), |d| Some(*d / 2)).take_while(|d| *d != 0)
+    }
+}
+
+/// Shrinks integers toward zero, or toward the target given to `shrink_towards`.  The target is
+/// tried first, then values ever closer to the original, and a negative distance from the target
+/// is tried as a positive one.
 #[derive(Clone)]
-pub struct IntegerShrinker<T>(PhantomData<T>);
+pub struct IntegerShrinker<T> {
+    target: T
+}
 
-impl <T> IntegerShrinker<T> where IntegerShrinker<T>: Shrink
+impl <T: Zero> IntegerShrinker<T> where IntegerShrinker<T>: Shrink
 {
-    pub fn new() -> Self { IntegerShrinker(PhantomData) }
+    pub fn new() -> Self { IntegerShrinker { target: T::zero() } }
+
+    /// Shrinks toward `target` rather than zero, for domains where some other value is simplest.
+    pub fn shrink_towards(self, target: T) -> Self {
+        IntegerShrinker { target: target }
+    }
 }
 
 macro_rules! int_impls {
-    ($($ty:ty),*) => {
+    ($($ty:ident: $uty:ident),*) => {
         $(
             impl Shrink for IntegerShrinker<$ty>
             {
@@ -105,38 +165,73 @@ macro_rules! int_impls {
                 type Iterator = Box<Iterator<Item=Self::Item>>;
 
                 fn shrink(&self, v: &$ty) -> Self::Iterator {
-                    let v = *v;
-                    let mut initials = vec![0];
-                    if v < 0 { initials.push(-v); }
-                    initials.push(v/2);
-                    let range = 1..(v.abs());
-                    if v < 0 {
-                        Box::new(
-                            initials.into_iter()
-                                .chain(range.scan(v, |&mut v, r| Some(v + r)))
-                                .filter(move |x| x.abs() <= v.abs() && *x != v)
-                        )
-                    } else {
-                        Box::new(
-                            initials.into_iter()
-                                .chain(range.scan(v, |&mut v, r| Some(v - r)))
-                                .filter(move |x| x.abs() <= v.abs() && *x != v)
-                        )
+                    let (v, target) = (*v, self.target);
+                    // The distance always fits in the unsigned type, and half of it in this one.
+                    let distance = match v < target {
+                        true => target.wrapping_sub(v) as $uty,
+                        false => v.wrapping_sub(target) as $uty
+                    };
+                    let mut initials = vec![target];
+                    if v < target && distance <= <$ty>::max_value() as $uty {
+                        initials.extend(target.checked_add(distance as $ty));
                     }
+                    Box::new(
+                        initials.into_iter()
+                            .chain(halvings!(distance).map(move |d| match v < target {
+                                true => v + d as $ty,
+                                false => v - d as $ty
+                            }))
+                            .filter(move |x| *x != v)
+                    )
                 }
             }
         )*
     }
 }
 
-int_impls! { i8, i16, i32, i64, isize }
+int_impls! { i8: u8, i16: u16, i32: u32, i64: u64, i128: u128, isize: usize }
 
+impl Shrink for IntegerShrinker<BigInt>
+{
+    type Item = BigInt;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, v: &BigInt) -> Self::Iterator {
+        let (v, target) = (v.clone(), self.target.clone());
+        let below = v < target;
+        let distance = (&v - &target).abs();
+        let mut initials = vec![target.clone()];
+        if below { initials.push(&target + &distance); }
+        let original = v.clone();
+        Box::new(
+            initials.into_iter()
+                .chain(
+                    iter::successors(Some(&distance >> 1), |d| Some(d >> 1))
+                        .take_while(|d| !d.is_zero())
+                        .map(move |d| match below {
+                            true => &v + d,
+                            false => &v - d
+                        })
+                )
+                .filter(move |x| *x != original)
+        )
+    }
+}
+
+/// Shrinks unsigned integers toward zero, or toward the target given to `shrink_towards`.
 #[derive(Clone)]
-pub struct UnsignedIntegerShrinker<T>(PhantomData<T>);
+pub struct UnsignedIntegerShrinker<T> {
+    target: T
+}
 
-impl <T> UnsignedIntegerShrinker<T> where UnsignedIntegerShrinker<T>: Shrink
+impl <T: Zero> UnsignedIntegerShrinker<T> where UnsignedIntegerShrinker<T>: Shrink
 {
-    pub fn new() -> Self { UnsignedIntegerShrinker(PhantomData) }
+    pub fn new() -> Self { UnsignedIntegerShrinker { target: T::zero() } }
+
+    /// Shrinks toward `target` rather than zero, for domains where some other value is simplest.
+    pub fn shrink_towards(self, target: T) -> Self {
+        UnsignedIntegerShrinker { target: target }
+    }
 }
 
 macro_rules! uint_impls {
@@ -148,11 +243,18 @@ macro_rules! uint_impls {
                 type Iterator = Box<Iterator<Item=Self::Item>>;
 
                 fn shrink(&self, v: &$ty) -> Self::Iterator {
-                    let v = *v;
+                    let (v, target) = (*v, self.target);
+                    let distance = match v < target {
+                        true => target - v,
+                        false => v - target
+                    };
                     Box::new(
-                        vec![0, v/2].into_iter()
-                            .chain((0..v).scan(v, |&mut v, r| Some(v - r)))
-                            .filter(move |x| *x < v)
+                        iter::once(target)
+                            .chain(halvings!(distance).map(move |d| match v < target {
+                                true => v + d,
+                                false => v - d
+                            }))
+                            .filter(move |x| *x != v)
                     )
                 }
             }
@@ -160,11 +262,96 @@ macro_rules! uint_impls {
     }
 }
 
-uint_impls! { u8, u16, u32, u64, usize }
+uint_impls! { u8, u16, u32, u64, u128, usize }
+
+impl Shrink for UnsignedIntegerShrinker<BigUint>
+{
+    type Item = BigUint;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, v: &BigUint) -> Self::Iterator {
+        let (v, target) = (v.clone(), self.target.clone());
+        let below = v < target;
+        let distance = match below {
+            true => &target - &v,
+            false => &v - &target
+        };
+        let original = v.clone();
+        Box::new(
+            iter::once(target)
+                .chain(
+                    iter::successors(Some(&distance >> 1), |d| Some(d >> 1))
+                        .take_while(|d| !d.is_zero())
+                        .map(move |d| match below {
+                            true => &v + d,
+                            false => &v - d
+                        })
+                )
+                .filter(move |x| *x != original)
+        )
+    }
+}
+
+/// Shrinks floats toward zero, or toward the target given to `shrink_towards`.  The target is
+/// tried first, then the whole number of steps from it, then values ever closer to the original.
+/// Infinities and NaN aren't shrunk.
+#[derive(Clone)]
+pub struct FloatShrinker<T> {
+    target: T
+}
+
+impl <T: Float> FloatShrinker<T> where FloatShrinker<T>: Shrink
+{
+    pub fn new() -> Self { FloatShrinker { target: T::zero() } }
+
+    /// Shrinks toward `target` rather than zero, for domains where some other value is simplest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` is infinite or NaN, as no finite value gets any closer to it.
+    pub fn shrink_towards(self, target: T) -> Self {
+        assert!(target.is_finite(), "FloatShrinker can only shrink toward a finite target");
+        FloatShrinker { target: target }
+    }
+}
+
+macro_rules! float_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Shrink for FloatShrinker<$ty>
+            {
+                type Item = $ty;
+                type Iterator = Box<Iterator<Item=Self::Item>>;
+
+                fn shrink(&self, v: &$ty) -> Self::Iterator {
+                    let (v, target) = (*v, self.target);
+                    if !v.is_finite() { return Box::new(iter::empty()) }
+                    let mut initials = vec![target];
+                    let whole = target + (v - target).trunc();
+                    if whole.is_finite() && whole != target { initials.push(whole); }
+                    // Halving the distance from each end avoids overflowing for far apart values.
+                    let half = v / 2.0 - target / 2.0;
+                    Box::new(
+                        initials.into_iter()
+                            .chain(
+                                iter::successors(Some(half), |d| Some(*d / 2.0))
+                                    .map(move |d| v - d)
+                                    .take_while(move |x| *x != v)
+                            )
+                            .filter(move |x| *x != v)
+                    )
+                }
+            }
+        )*
+    }
+}
+
+float_impls! { f32, f64 }
 
 #[derive(Clone)]
 pub struct FromIteratorShrinker<C, S> {
     shrinker: S,
+    min_len: usize,
     _marker: PhantomData<C>
 }
 
@@ -172,7 +359,12 @@ impl <C, S> FromIteratorShrinker<C, S>
     where FromIteratorShrinker<C, S>: Shrink
 {
     pub fn new(shrinker: S) -> Self {
-        FromIteratorShrinker { shrinker: shrinker, _marker: PhantomData }
+        FromIteratorShrinker { shrinker: shrinker, min_len: 0, _marker: PhantomData }
+    }
+
+    /// A shrinker that never removes elements from a collection with `min_len` or fewer elements.
+    pub fn with_min_len(shrinker: S, min_len: usize) -> Self {
+        FromIteratorShrinker { shrinker: shrinker, min_len: min_len, _marker: PhantomData }
     }
 }
 
@@ -187,12 +379,14 @@ impl <C, S> Shrink for FromIteratorShrinker<C, S>
     fn shrink(&self, v: &C) -> Self::Iterator {
         let elements = v.clone().into_iter().collect::<Vec<_>>();
         let elements_len = elements.len();
+        let max_removed = elements_len.saturating_sub(self.min_len);
         let shrinker = self.shrinker.clone();
 
         Box::new(
             (0..(elements.len()))
                 .map(|shift| elements_len >> shift)
                 .take_while(|&k| k > 0)
+                .filter(move |&k| k <= max_removed)
                 .scan(elements.clone(), |elements, k| Some(Removes::new(elements.clone(), k)))
                 .fold(Box::new(iter::empty()) as Box<Iterator<Item=C>>, |it, next| Box::new(it.chain(next)) )
                 .chain(
@@ -216,6 +410,522 @@ impl <C, S> Shrink for FromIteratorShrinker<C, S>
     }
 }
 
+/// Shrinks recursive values such as trees.  Candidates are first the immediate subterms of a
+/// value, then the value with one of its subterms shrunk in place, recursively.
+///
+/// `subterms` lists the immediate subterms of a value that have the same type as it, such as the
+/// children of a tree node.  `rebuild` puts a value back together from a list of its subterms in
+/// the same order, which is how a subterm shrunk in place is put back into its parent.
+pub struct SubtermShrinker<T, F, G> {
+    subterms: Arc<F>,
+    rebuild: Arc<G>,
+    _marker: PhantomData<fn() -> T>
+}
+
+impl <T, F, G> Clone for SubtermShrinker<T, F, G> {
+    fn clone(&self) -> Self {
+        SubtermShrinker { subterms: self.subterms.clone(), rebuild: self.rebuild.clone(), _marker: PhantomData }
+    }
+}
+
+impl <T, F, G> SubtermShrinker<T, F, G>
+    where F: Fn(&T) -> Vec<T>,
+          G: Fn(&T, Vec<T>) -> T
+{
+    pub fn new(subterms: F, rebuild: G) -> Self {
+        SubtermShrinker { subterms: Arc::new(subterms), rebuild: Arc::new(rebuild), _marker: PhantomData }
+    }
+}
+
+impl <T, F, G> Shrink for SubtermShrinker<T, F, G>
+    where T: Clone + 'static,
+          F: Fn(&T) -> Vec<T> + 'static,
+          G: Fn(&T, Vec<T>) -> T + 'static
+{
+    type Item = T;
+    type Iterator = Box<Iterator<Item=T>>;
+
+    fn shrink(&self, value: &T) -> Self::Iterator {
+        let subterms = (self.subterms)(value);
+        let shrinker = self.clone();
+        let value = value.clone();
+        let in_place = (0..subterms.len())
+            .flat_map({
+                let subterms = subterms.clone();
+                move |idx| {
+                    let (subterms, value, rebuild) = (subterms.clone(), value.clone(), shrinker.rebuild.clone());
+                    shrinker.shrink(&subterms[idx]).map(move |shrunk| {
+                        let mut subterms = subterms.clone();
+                        subterms[idx] = shrunk;
+                        rebuild(&value, subterms)
+                    })
+                }
+            });
+        Box::new(subterms.into_iter().chain(in_place))
+    }
+}
+
+/// Shrinks a `T` by shrinking the value `from` extracts from it and converting each candidate
+/// back with `to`.  This is how wrappers such as `Box<T>` shrink through to what they wrap.
+pub struct MappedShrinker<S, F, G> {
+    shrinker: S,
+    to: Arc<F>,
+    from: Arc<G>
+}
+
+impl <S: Clone, F, G> Clone for MappedShrinker<S, F, G> {
+    fn clone(&self) -> Self {
+        MappedShrinker { shrinker: self.shrinker.clone(), to: self.to.clone(), from: self.from.clone() }
+    }
+}
+
+impl <S, F, G, T> MappedShrinker<S, F, G>
+    where S: Shrink,
+          F: Fn(S::Item) -> T,
+          G: Fn(&T) -> S::Item
+{
+    pub fn new(shrinker: S, to: F, from: G) -> Self {
+        MappedShrinker { shrinker: shrinker, to: Arc::new(to), from: Arc::new(from) }
+    }
+}
+
+impl <S, F, G, T> Shrink for MappedShrinker<S, F, G>
+    where S: Shrink,
+          S::Iterator: 'static,
+          F: Fn(S::Item) -> T + 'static,
+          G: Fn(&T) -> S::Item
+{
+    type Item = T;
+    type Iterator = Box<Iterator<Item=T>>;
+
+    fn shrink(&self, value: &T) -> Self::Iterator {
+        let to = self.to.clone();
+        Box::new(self.shrinker.shrink(&(self.from)(value)).map(move |x| to(x)))
+    }
+}
+
+pub struct FilteredShrinker<S, P> {
+    shrinker: S,
+    predicate: Arc<P>
+}
+
+impl <S: Clone, P> Clone for FilteredShrinker<S, P> {
+    fn clone(&self) -> Self {
+        FilteredShrinker { shrinker: self.shrinker.clone(), predicate: self.predicate.clone() }
+    }
+}
+
+impl <S, P> Shrink for FilteredShrinker<S, P>
+    where S: Shrink,
+          S::Iterator: 'static,
+          P: Fn(&S::Item) -> bool + 'static
+{
+    type Item = S::Item;
+    type Iterator = Box<Iterator<Item=S::Item>>;
+
+    fn shrink(&self, value: &S::Item) -> Self::Iterator {
+        let predicate = self.predicate.clone();
+        Box::new(self.shrinker.shrink(value).filter(move |x| predicate(x)))
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainedShrinker<S, T> {
+    first: S,
+    second: T
+}
+
+impl <S, T> Shrink for ChainedShrinker<S, T>
+    where S: Shrink,
+          T: Shrink<Item=S::Item>
+{
+    type Item = S::Item;
+    type Iterator = iter::Chain<S::Iterator, T::Iterator>;
+
+    fn shrink(&self, value: &S::Item) -> Self::Iterator {
+        self.first.shrink(value).chain(self.second.shrink(value))
+    }
+}
+
+#[derive(Clone)]
+pub struct LimitedShrinker<S> {
+    shrinker: S,
+    limit: usize
+}
+
+impl <S: Shrink> Shrink for LimitedShrinker<S> {
+    type Item = S::Item;
+    type Iterator = iter::Take<S::Iterator>;
+
+    fn shrink(&self, value: &S::Item) -> Self::Iterator {
+        self.shrinker.shrink(value).take(self.limit)
+    }
+}
+
+/// Shrinks arrays one element at a time, keeping their length.
+#[derive(Clone)]
+pub struct ArrayShrinker<S, const N: usize> {
+    shrinker: S
+}
+
+impl <S: Shrink, const N: usize> ArrayShrinker<S, N> {
+    pub fn new(shrinker: S) -> Self { ArrayShrinker { shrinker: shrinker } }
+}
+
+impl <S, const N: usize> Shrink for ArrayShrinker<S, N>
+    where S: Shrink + 'static,
+          S::Item: Clone + 'static
+{
+    type Item = [S::Item; N];
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, value: &Self::Item) -> Self::Iterator {
+        let value = value.clone();
+        let shrinker = self.shrinker.clone();
+        Box::new((0..N).flat_map(move |idx| {
+            let value = value.clone();
+            shrinker.shrink(&value[idx]).map(move |x| {
+                let mut value = value.clone();
+                value[idx] = x;
+                value
+            })
+        }))
+    }
+}
+
+/// Shrinks the bounds of a range first to a range starting and ending at `start`, and then
+/// pairwise, skipping any candidate whose start is after its end.
+fn shrink_bounds<S>(bounds: &(S, S), start: S::Item, end: S::Item) -> Box<Iterator<Item=(S::Item, S::Item)>>
+    where S: Shrink + 'static,
+          S::Item: PartialOrd + Clone + 'static
+{
+    let collapsed = match start < end {
+        true => Some((start.clone(), start.clone())),
+        false => None
+    };
+    Box::new(
+        collapsed.into_iter()
+            .chain(bounds.shrink(&(start, end)).filter(|&(ref start, ref end)| start <= end))
+    )
+}
+
+/// Shrinks ranges `start..end` with `start <= end` toward the empty range `start..start`.
+#[derive(Clone)]
+pub struct RangeShrinker<S> {
+    bounds: (S, S)
+}
+
+impl <S: Shrink + Clone> RangeShrinker<S> {
+    pub fn new(shrinker: S) -> Self { RangeShrinker { bounds: (shrinker.clone(), shrinker) } }
+}
+
+impl <S> Shrink for RangeShrinker<S>
+    where S: Shrink + 'static,
+          S::Item: PartialOrd + Clone + 'static
+{
+    type Item = Range<S::Item>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, range: &Self::Item) -> Self::Iterator {
+        Box::new(shrink_bounds(&self.bounds, range.start.clone(), range.end.clone()).map(|(start, end)| start..end))
+    }
+}
+
+/// Shrinks ranges `start..=end` with `start <= end` toward the single element range
+/// `start..=start`.
+#[derive(Clone)]
+pub struct RangeInclusiveShrinker<S> {
+    bounds: (S, S)
+}
+
+impl <S: Shrink + Clone> RangeInclusiveShrinker<S> {
+    pub fn new(shrinker: S) -> Self { RangeInclusiveShrinker { bounds: (shrinker.clone(), shrinker) } }
+}
+
+impl <S> Shrink for RangeInclusiveShrinker<S>
+    where S: Shrink + 'static,
+          S::Item: PartialOrd + Clone + 'static
+{
+    type Item = RangeInclusive<S::Item>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, range: &Self::Item) -> Self::Iterator {
+        Box::new(
+            shrink_bounds(&self.bounds, range.start().clone(), range.end().clone())
+                .map(|(start, end)| RangeInclusive::new(start, end))
+        )
+    }
+}
+
+/// Shrinks maps, or any other collection of key-value pairs, by removing entries and shrinking
+/// values.  Keys are never changed, as changing a key can merge two entries.
+pub type MapShrinker<C, K, SV> = FromIteratorShrinker<C, (Empty<K>, SV)>;
+
+impl <C, K, SV> FromIteratorShrinker<C, (Empty<K>, SV)>
+    where FromIteratorShrinker<C, (Empty<K>, SV)>: Shrink
+{
+    pub fn map_values(values: SV) -> Self {
+        FromIteratorShrinker::new((Empty::empty(), values))
+    }
+}
+
+/// Shrinks a `Vec` whose elements have distinct keys, skipping any candidate in which two
+/// elements share a key.
+pub struct UniqueShrinker<S: Shrink, F> {
+    shrinker: FromIteratorShrinker<Vec<S::Item>, S>,
+    key: Arc<F>
+}
+
+impl <S: Shrink, F> Clone for UniqueShrinker<S, F>
+    where S::Item: Clone
+{
+    fn clone(&self) -> Self {
+        UniqueShrinker { shrinker: self.shrinker.clone(), key: self.key.clone() }
+    }
+}
+
+impl <S, F, K> UniqueShrinker<S, F>
+    where S: Shrink,
+          F: Fn(&S::Item) -> K,
+          K: Hash + Eq
+{
+    pub fn new(shrinker: S, key: F) -> Self {
+        UniqueShrinker {
+            shrinker: FromIteratorShrinker { shrinker: shrinker, min_len: 0, _marker: PhantomData },
+            key: Arc::new(key)
+        }
+    }
+}
+
+impl <S, F, K> Shrink for UniqueShrinker<S, F>
+    where S: Shrink + 'static,
+          S::Item: Clone + 'static,
+          F: Fn(&S::Item) -> K + 'static,
+          K: Hash + Eq
+{
+    type Item = Vec<S::Item>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, v: &Self::Item) -> Self::Iterator {
+        let key = self.key.clone();
+        Box::new(
+            self.shrinker.shrink(v)
+                .filter(move |candidate| {
+                    let mut seen = HashSet::new();
+                    candidate.iter().all(|x| seen.insert(key(x)))
+                })
+        )
+    }
+}
+
+/// Shrinks a sorted `Vec`, re-sorting each candidate.
+#[derive(Clone)]
+pub struct SortedShrinker<S: Shrink> {
+    shrinker: FromIteratorShrinker<Vec<S::Item>, S>
+}
+
+impl <S: Shrink> SortedShrinker<S>
+    where S::Item: Ord
+{
+    pub fn new(shrinker: S) -> Self {
+        SortedShrinker {
+            shrinker: FromIteratorShrinker { shrinker: shrinker, min_len: 0, _marker: PhantomData }
+        }
+    }
+}
+
+impl <S> Shrink for SortedShrinker<S>
+    where S: Shrink + 'static,
+          S::Item: Ord + Clone + 'static
+{
+    type Item = Vec<S::Item>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, v: &Self::Item) -> Self::Iterator {
+        Box::new(
+            self.shrinker.shrink(v)
+                .map(|mut candidate| { candidate.sort(); candidate })
+        )
+    }
+}
+
+/// Shrinks collections whose order doesn't carry meaning, such as `HashSet`, `HashMap` and
+/// `BinaryHeap`.  Their elements are sorted before shrinking, rather than taken in iteration
+/// order, so the same failure shrinks to the same minimal counterexample on every run.
+///
+/// `new` and `map_values` sort by `Ord`.  `by_hash` and `map_values_by_hash` sort by a hash
+/// that doesn't change between runs, for elements that can only be hashed.  Elements whose
+/// hashes collide are left in iteration order.
+#[derive(Clone)]
+pub struct CanonicalShrinker<C, S: Shrink> {
+    shrinker: FromIteratorShrinker<Vec<S::Item>, S>,
+    compare: fn(&S::Item, &S::Item) -> Ordering,
+    _marker: PhantomData<C>
+}
+
+impl <C, S: Shrink> CanonicalShrinker<C, S>
+    where S::Item: Ord
+{
+    pub fn new(shrinker: S) -> Self {
+        CanonicalShrinker {
+            shrinker: FromIteratorShrinker { shrinker: shrinker, min_len: 0, _marker: PhantomData },
+            compare: Ord::cmp,
+            _marker: PhantomData
+        }
+    }
+}
+
+impl <C, S: Shrink> CanonicalShrinker<C, S>
+    where S::Item: Hash
+{
+    pub fn by_hash(shrinker: S) -> Self {
+        CanonicalShrinker {
+            shrinker: FromIteratorShrinker { shrinker: shrinker, min_len: 0, _marker: PhantomData },
+            compare: compare_hashes,
+            _marker: PhantomData
+        }
+    }
+}
+
+/// A hash of `value` that is the same on every run, unlike that of a `HashSet`'s `RandomState`.
+fn fixed_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn compare_hashes<T: Hash>(a: &T, b: &T) -> Ordering { fixed_hash(a).cmp(&fixed_hash(b)) }
+
+fn compare_keys<K: Ord, V>(a: &(K, V), b: &(K, V)) -> Ordering { a.0.cmp(&b.0) }
+
+fn compare_key_hashes<K: Hash, V>(a: &(K, V), b: &(K, V)) -> Ordering { fixed_hash(&a.0).cmp(&fixed_hash(&b.0)) }
+
+/// Shrinks maps whose iteration order doesn't carry meaning, ordering entries by their key.
+pub type CanonicalMapShrinker<C, K, SV> = CanonicalShrinker<C, (Empty<K>, SV)>;
+
+impl <C, K, SV> CanonicalShrinker<C, (Empty<K>, SV)>
+    where K: Ord,
+          SV: Shrink,
+          (Empty<K>, SV): Shrink<Item=(K, SV::Item)>
+{
+    pub fn map_values(values: SV) -> Self {
+        CanonicalShrinker {
+            shrinker: FromIteratorShrinker { shrinker: (Empty::empty(), values), min_len: 0, _marker: PhantomData },
+            compare: compare_keys,
+            _marker: PhantomData
+        }
+    }
+}
+
+impl <C, K, SV> CanonicalShrinker<C, (Empty<K>, SV)>
+    where K: Hash,
+          SV: Shrink,
+          (Empty<K>, SV): Shrink<Item=(K, SV::Item)>
+{
+    pub fn map_values_by_hash(values: SV) -> Self {
+        CanonicalShrinker {
+            shrinker: FromIteratorShrinker { shrinker: (Empty::empty(), values), min_len: 0, _marker: PhantomData },
+            compare: compare_key_hashes,
+            _marker: PhantomData
+        }
+    }
+}
+
+impl <C, S> Shrink for CanonicalShrinker<C, S>
+    where S: Shrink + 'static,
+          S::Item: Clone + 'static,
+          C: FromIterator<S::Item> + IntoIterator<Item=S::Item> + Clone + 'static
+{
+    type Item = C;
+    type Iterator = Box<Iterator<Item=C>>;
+
+    fn shrink(&self, v: &C) -> Self::Iterator {
+        let mut elements = v.clone().into_iter().collect::<Vec<_>>();
+        elements.sort_by(self.compare);
+        Box::new(
+            self.shrinker.shrink(&elements)
+                .map(|candidate| candidate.into_iter().collect())
+        )
+    }
+}
+
+/// Shrinks a subset or sublist of another collection by removing elements only, so that every
+/// candidate is still a subset or sublist of the original collection.
+pub type SubsetShrinker<C, T> = FromIteratorShrinker<C, Empty<T>>;
+
+impl <C, T> FromIteratorShrinker<C, Empty<T>>
+    where FromIteratorShrinker<C, Empty<T>>: Shrink
+{
+    pub fn subset() -> Self {
+        FromIteratorShrinker::new(Empty::empty())
+    }
+}
+
+/// Shrinks a permutation of `source` back toward `source` itself.  Each candidate moves the
+/// element that belongs at some position into place by swapping it with the element found there.
+/// Only swaps of pairs that are out of order relative to `source` are tried, so every candidate
+/// remains a permutation and has strictly fewer inversions, and there are at most as many
+/// candidates as elements.  Values that aren't a permutation of `source` have no candidates.
+#[derive(Clone)]
+pub struct PermutationShrinker<T: Hash + Eq> {
+    positions: Arc<HashMap<T, Vec<usize>>>,
+    len: usize
+}
+
+impl <T: Hash + Eq> PermutationShrinker<T> {
+    pub fn new(source: Vec<T>) -> Self {
+        let len = source.len();
+        let mut positions = HashMap::new();
+        for (idx, x) in source.into_iter().enumerate() {
+            positions.entry(x).or_insert_with(Vec::new).push(idx);
+        }
+        PermutationShrinker { positions: Arc::new(positions), len: len }
+    }
+
+    /// The position in `source` of each element of `v`.  Equal elements are matched up in order.
+    fn positions(&self, v: &[T]) -> Option<Vec<usize>> {
+        if v.len() != self.len { return None }
+        let mut used = HashMap::new();
+        v.iter().map(|x| {
+            let used = used.entry(x).or_insert(0);
+            let idx = self.positions.get(x).and_then(|positions| positions.get(*used)).cloned();
+            *used += 1;
+            idx
+        }).collect()
+    }
+}
+
+impl <T: Hash + Eq + Clone + 'static> Shrink for PermutationShrinker<T> {
+    type Item = Vec<T>;
+    type Iterator = Box<Iterator<Item=Self::Item>>;
+
+    fn shrink(&self, v: &Vec<T>) -> Self::Iterator {
+        let positions = match self.positions(v) {
+            Some(positions) => positions,
+            None => return Box::new(iter::empty())
+        };
+        // `belongs_at[i]` is the index in `v` of the element that belongs at position `i`.
+        let mut belongs_at = vec![0; positions.len()];
+        for (idx, &position) in positions.iter().enumerate() {
+            belongs_at[position] = idx;
+        }
+        let v = v.clone();
+        Box::new(
+            (0..v.len())
+                .map(move |i| (i, belongs_at[i]))
+                // Only elements found after the position they belong at are moved back into place,
+                // and only when the element they displace belongs after them, so every candidate
+                // puts an out-of-order pair in order.
+                .filter(move |&(i, j)| i < j && positions[i] > positions[j])
+                .map(move |(i, j)| {
+                    let mut candidate = v.clone();
+                    candidate.swap(i, j);
+                    candidate
+                })
+        )
+    }
+}
+
 struct Removes<T, C> {
     elements: Vec<T>,
     k: usize,
@@ -280,3 +990,15 @@ impl Shrink for DefaultShrinker<bool> {
         }
     }
 }
+
+impl Shrink for DefaultShrinker<Ordering> {
+    type Item = Ordering;
+    type Iterator = Box<Iterator<Item=Ordering>>;
+
+    fn shrink(&self, value: &Ordering) -> Self::Iterator {
+        match *value {
+            Ordering::Equal => Box::new(iter::empty()),
+            _ => Box::new(iter::once(Ordering::Equal))
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use testable::{TestResult, TestStatus};
+
+use std::fmt::{self, Write};
+
+/// A record of the path `QuickCheck` took through the shrink tree of a failing test case.
+///
+/// Each step holds a failing input and every candidate that was tried while shrinking it.  The
+/// first failing candidate is accepted and becomes the next step, so for every step except the
+/// last the accepted candidate is the final entry in `candidates`.
+#[derive(Clone)]
+pub struct ShrinkTrace {
+    pub steps: Vec<ShrinkStep>
+}
+
+#[derive(Clone)]
+pub struct ShrinkStep {
+    pub result: TestResult,
+    pub candidates: Vec<TestResult>
+}
+
+impl ShrinkTrace {
+    pub fn new() -> Self {
+        ShrinkTrace { steps: vec![] }
+    }
+
+    pub fn push_step(&mut self, result: TestResult) {
+        self.steps.push(ShrinkStep { result: result, candidates: vec![] });
+    }
+
+    pub fn push_candidate(&mut self, candidate: TestResult) {
+        self.steps.last_mut()
+            .expect("A candidate must be recorded after the step it shrinks")
+            .candidates.push(candidate);
+    }
+
+    /// The number of candidates tried over the whole shrink.
+    pub fn candidates_tried(&self) -> usize {
+        self.steps.iter().map(|step| step.candidates.len()).sum()
+    }
+
+    fn is_accepted(&self, step: usize, candidate: usize) -> bool {
+        step + 1 < self.steps.len() && candidate + 1 == self.steps[step].candidates.len()
+    }
+
+    /// Render the trace as an indented tree, one tried input per line.  Candidates are nested
+    /// beneath the failing input they were shrunk from.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        if let Some(first) = self.steps.first() {
+            let _ = writeln!(out, "{:?}: {}", first.result.status, first.result.input);
+        }
+        for (depth, step) in self.steps.iter().enumerate() {
+            let indent = "  ".repeat(depth + 1);
+            for (idx, candidate) in step.candidates.iter().enumerate() {
+                let marker = if self.is_accepted(depth, idx) { " (accepted)" } else { "" };
+                let _ = writeln!(out, "{}{:?}: {}{}", indent, candidate.status, candidate.input, marker);
+            }
+        }
+        out
+    }
+
+    /// Render the trace as a Graphviz DOT digraph.  Failing inputs are drawn in red and the
+    /// accepted shrink steps are drawn in bold.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph shrink {\n");
+        let mut next_id = 0;
+        let mut parent = None;
+        for (depth, step) in self.steps.iter().enumerate() {
+            let step_id = match parent {
+                Some(id) => id,
+                None => {
+                    write_dot_node(&mut out, next_id, &step.result);
+                    next_id += 1;
+                    next_id - 1
+                }
+            };
+            parent = None;
+            for (idx, candidate) in step.candidates.iter().enumerate() {
+                let id = next_id;
+                next_id += 1;
+                write_dot_node(&mut out, id, candidate);
+                let accepted = self.is_accepted(depth, idx);
+                let _ = writeln!(out, "    n{} -> n{}{};", step_id, id, if accepted { " [style=bold]" } else { "" });
+                if accepted { parent = Some(id); }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn write_dot_node(out: &mut String, id: usize, result: &TestResult) {
+    let label = format!("{:?}: {}", result.status, result.input)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    let colour = match result.status {
+        TestStatus::Fail => "red",
+        TestStatus::Pass => "black",
+        TestStatus::Discard => "grey"
+    };
+    let _ = writeln!(out, "    n{} [label=\"{}\", color={}];", id, label, colour);
+}
+
+impl fmt::Display for ShrinkTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testable::{TestResult, TestStatus};
+
+    fn result(input: &str, status: TestStatus) -> TestResult {
+        TestResult { input: input.to_string(), status: status }
+    }
+
+    fn trace() -> ShrinkTrace {
+        let mut trace = ShrinkTrace::new();
+        trace.push_step(result("4", TestStatus::Fail));
+        trace.push_candidate(result("0", TestStatus::Pass));
+        trace.push_candidate(result("2", TestStatus::Fail));
+        trace.push_step(result("2", TestStatus::Fail));
+        trace.push_candidate(result("0", TestStatus::Pass));
+        trace.push_candidate(result("1", TestStatus::Pass));
+        trace
+    }
+
+    #[test]
+    fn text_nests_candidates() {
+        assert_eq!(
+            trace().to_text(),
+            "Fail: 4\n  Pass: 0\n  Fail: 2 (accepted)\n    Pass: 0\n    Pass: 1\n"
+        );
+        assert_eq!(trace().candidates_tried(), 4);
+    }
+
+    #[test]
+    fn dot_marks_accepted_edges() {
+        let dot = trace().to_dot();
+        assert!(dot.starts_with("digraph shrink {\n"));
+        assert!(dot.contains("n0 -> n2 [style=bold];"));
+        assert!(dot.contains("n2 -> n3;"));
+        assert!(dot.contains("n2 [label=\"Fail: 2\", color=red];"));
+    }
+}
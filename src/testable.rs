@@ -29,6 +29,24 @@ impl <'a, T: Testable> Testable for &'a T {
     fn test<R: Rng>(&self, ctx: &mut GenerateCtx<R>) -> Rose<TestResult> {
         (*self).test(ctx)
     }
+
+    #[inline]
+    fn is_expected_to_fail(&self) -> bool {
+        (*self).is_expected_to_fail()
+    }
+}
+
+/// A `Testable` whose arguments can be enumerated, so that it can be checked against every
+/// input up to a given depth rather than against random inputs.
+pub trait ExhaustiveTestable: Testable {
+    fn test_exhaustive<'a>(&'a self, depth: usize) -> Box<Iterator<Item=TestResult> + 'a>;
+}
+
+impl <'a, T: ExhaustiveTestable> ExhaustiveTestable for &'a T {
+    #[inline]
+    fn test_exhaustive<'b>(&'b self, depth: usize) -> Box<Iterator<Item=TestResult> + 'b> {
+        (*self).test_exhaustive(depth)
+    }
 }
 
 pub trait IntoTestable {
@@ -72,6 +90,21 @@ impl <T: Testable> Testable for FailureExpectedTestable<T> {
     fn is_expected_to_fail(&self) -> bool { true }
 }
 
+impl <T, F> ExhaustiveTestable for ResizedTestable<T, F>
+    where T: ExhaustiveTestable,
+          F: Fn(usize) -> usize
+{
+    fn test_exhaustive<'a>(&'a self, depth: usize) -> Box<Iterator<Item=TestResult> + 'a> {
+        self.testable.test_exhaustive(depth)
+    }
+}
+
+impl <T: ExhaustiveTestable> ExhaustiveTestable for FailureExpectedTestable<T> {
+    fn test_exhaustive<'a>(&'a self, depth: usize) -> Box<Iterator<Item=TestResult> + 'a> {
+        self.0.test_exhaustive(depth)
+    }
+}
+
 impl <T: Testable> IntoTestable for T {
     type Testable = Self;
 
@@ -2,6 +2,7 @@ use quick_check::{
     quickcheck,
     quicktest,
     QuickCheck,
+    ExhaustiveQuickCheck,
     QuickCheckError
 };
 
@@ -9,6 +10,24 @@ use property::{
     Property
 };
 
+use arbitrary::{Arbitrary, Positive, NonNegative, NonZero, NonEmpty, Small, Large, Sorted, Ascii};
+use fun::Fun;
+use json::{Json, JsonGenerator, JsonShrinker};
+use regex::regex;
+use grammar::Grammar;
+use generate::{Generator, MappedGenerator, IntegerGenerator, UnsignedIntegerGenerator, vec_of, non_empty, unique, sorted, permutation_of, subset_of, sublist_of, recursive};
+use shrink::{Shrink, MappedShrinker, IntegerShrinker, UnsignedIntegerShrinker, FloatShrinker, FromIteratorShrinker, UniqueShrinker, SortedShrinker, PermutationShrinker, SubtermShrinker};
+
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::num::{Wrapping, NonZeroI32};
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
+use std::time::Duration;
+
+use num::bigint::{BigInt, BigUint};
+
 use testable::{
     IntoTestable
 };
@@ -179,3 +198,693 @@ fn failing_reverse_combine() {
         _ => assert!(false, "Test didn't fail: {:?}", result)
     }
 }
+
+#[test]
+fn parallel_reverse_reverse() {
+    fn prop(input: Vec<u8>) -> bool {
+        let mut revrev = input.clone();
+        revrev.reverse();
+        revrev.reverse();
+        revrev == input
+    }
+    let result = QuickCheck::new().threads(4).quicktest(prop as fn(Vec<u8>) -> bool);
+    assert_eq!(result.ok(), Some(100));
+}
+
+#[test]
+fn parallel_failing_reverse_combine() {
+    let prop = Property::<(Vec<isize>, Vec<isize>)>
+        ::new(|xs, ys| {
+            let concat = xs.iter().chain(ys.iter()).cloned().collect::<Vec<_>>();
+            concat.iter().rev().cloned().collect::<Vec<_>>() ==
+                xs.iter().rev().chain(ys.iter().rev()).cloned().collect::<Vec<_>>()
+        });
+
+    let result = QuickCheck::new().threads(4).quicktest(&prop);
+    match result {
+        Err(QuickCheckError::Failure {ref input, .. }) =>
+            assert!(input == "([0], [1])" || input == "([1], [0])", "Didn't get the expected shrunk result.  Instead found {:?}", result),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn parallel_failure_is_reproducible() {
+    let prop = Property::<(usize,)>::new(|n| n < 20);
+
+    let failure = |threads| match QuickCheck::new().threads(threads).seed(42).quicktest(&prop) {
+        Err(QuickCheckError::Failure { seed, size, .. }) => (seed, size),
+        result => panic!("Test didn't fail: {:?}", result)
+    };
+
+    assert_eq!(failure(1), failure(4));
+}
+
+#[test]
+fn parallel_outcome_matches_single_thread() {
+    // Sleep so that the workers' cases overlap.
+    let prop = Property::<(u8,)>::new(|n| { ::std::thread::sleep(Duration::from_millis(1)); n % 16 != 5 });
+
+    let outcome = |threads, seed| match QuickCheck::new().tests(20).threads(threads).seed(seed).quicktest(&prop) {
+        Ok(tests) => (tests, None),
+        Err(QuickCheckError::Failure { successful_tests, seed, .. }) => (successful_tests, Some(seed)),
+        result => panic!("Unexpected result: {:?}", result)
+    };
+
+    for seed in 0..20 {
+        assert_eq!(outcome(1, seed), outcome(8, seed));
+    }
+}
+
+#[test]
+fn parallel_expected_failure() {
+    let prop = Property::<(usize,)>::new(|n| n < 20).expect_failure();
+    QuickCheck::new().threads(4).quickcheck(prop);
+}
+
+#[test]
+fn shrink_trace_records_path() {
+    let prop = Property::<(usize,)>::new(|n| n < 20);
+
+    let mut quick_check = QuickCheck::new().trace_shrinks(true);
+    let result = quick_check.quicktest(&prop);
+    let trace = quick_check.shrink_trace().expect("A failing shrink should be traced");
+
+    assert_eq!(trace.steps.last().unwrap().result.input, "(20,)");
+    assert!(trace.candidates_tried() >= trace.steps.len() - 1);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(20,)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+    assert!(trace.to_text().contains("Pass: (19,)\n"));
+
+    // Tracing stays on across runs, but a passing run leaves no trace behind.
+    assert!(quick_check.quicktest(Property::<(usize,)>::new(|_| true)).is_ok());
+    assert!(quick_check.shrink_trace().is_none());
+    let _ = quick_check.quicktest(&prop);
+    assert!(quick_check.shrink_trace().is_some());
+}
+
+#[test]
+fn shrink_trace_is_off_by_default() {
+    let prop = Property::<(usize,)>::new(|n| n < 20);
+
+    let mut quick_check = QuickCheck::new();
+    let _ = quick_check.quicktest(&prop);
+    assert!(quick_check.shrink_trace().is_none());
+}
+
+#[test]
+fn exhaustive_reverse_reverse() {
+    fn prop(input: Vec<bool>) -> bool {
+        let mut revrev = input.clone();
+        revrev.reverse();
+        revrev.reverse();
+        revrev == input
+    }
+    let result = ExhaustiveQuickCheck::new(4).quicktest(prop as fn(Vec<bool>) -> bool);
+    assert_eq!(result.ok(), Some(31));
+}
+
+#[test]
+fn exhaustive_finds_minimal_failure() {
+    let prop = Property::<(Vec<u8>, Vec<u8>)>
+        ::new(|xs, ys| {
+            let concat = xs.iter().chain(ys.iter()).cloned().collect::<Vec<_>>();
+            concat.iter().rev().cloned().collect::<Vec<_>>() ==
+                xs.iter().rev().chain(ys.iter().rev()).cloned().collect::<Vec<_>>()
+        });
+
+    let result = ExhaustiveQuickCheck::new(2).quicktest(&prop);
+    match result {
+        Err(QuickCheckError::Failure {ref input, .. }) => assert_eq!(input, "([0], [1])"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn exhaustive_failure_is_smallest_in_total() {
+    let prop = Property::<(u8, u8)>::new(|x, y| x == 0 && y < 3);
+
+    // `(0, 3)` comes first lexicographically, but `(1, 0)` is smaller in total.
+    let result = ExhaustiveQuickCheck::new(3).quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(1, 0)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn exhaustive_when_property() {
+    let prop = Property::<(u8, u8)>
+        ::when(|x, y| x <= y)
+        .property(|x, y| ::std::cmp::max(x, y) == y);
+
+    assert_eq!(ExhaustiveQuickCheck::new(3).quicktest(prop).ok(), Some(10));
+}
+
+#[test]
+fn prop_filter_with_arbitrary_function() {
+    fn prop(f: Fun<u8, bool>, xs: Vec<u8>) -> bool {
+        let kept = xs.iter().filter(|x| f.apply(x)).count();
+        let dropped = xs.iter().filter(|x| !f.apply(x)).count();
+        kept + dropped == xs.len()
+    }
+    quickcheck(prop as fn(Fun<u8, bool>, Vec<u8>) -> bool);
+}
+
+#[test]
+fn failing_function_shrinks_to_table() {
+    fn prop(f: Fun<usize, bool>, x: usize) -> bool {
+        f.apply(&x) == f.apply(&0)
+    }
+    let result = quicktest(prop as fn(Fun<usize, bool>, usize) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) =>
+            assert!(input.starts_with("({") && input.matches(" => ").count() == 2,
+                    "Didn't get the expected shrunk result.  Instead found {:?}", result),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn length_controlled_vec_never_shrinks_below_minimum() {
+    let prop = Property::<(Vec<u8>,)>
+        ::for_all_shrink(
+            (vec_of(u8::arbitrary(), 2..10),),
+            (FromIteratorShrinker::with_min_len(u8::shrink(), 2),))
+        .property(|xs| xs.len() < 2);
+
+    let result = quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "([0, 0],)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+
+    let generator = non_empty(u8::arbitrary());
+    let shrinker = generator.shrinker(u8::shrink());
+    let prop = Property::<(Vec<u8>,)>
+        ::for_all_shrink((generator,), (shrinker,))
+        .property(|xs| xs.len() > 100);
+
+    let result = quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "([0],)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn non_empty_vec_has_a_head() {
+    let prop = Property::<(Vec<usize>,)>
+        ::for_all((non_empty(usize::arbitrary()),))
+        .property(|xs| xs.first().is_some());
+    quickcheck(prop);
+}
+
+#[test]
+fn unique_vec_shrinks_without_duplicates() {
+    let prop = Property::<(Vec<u8>,)>
+        ::for_all_shrink(
+            (unique(Vec::<u8>::arbitrary()),),
+            (UniqueShrinker::new(u8::shrink(), |x: &u8| *x),))
+        .property(|xs| xs.len() < 3);
+
+    let result = quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => {
+            let mut digits = input.chars().filter(|c| c.is_digit(10)).collect::<Vec<_>>();
+            digits.sort();
+            assert_eq!(digits, vec!['0', '1', '2'], "Unexpected shrunk input {}", input)
+        },
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn sorted_vec_is_sorted() {
+    let prop = Property::<(Vec<isize>,)>
+        ::for_all_shrink((sorted(Vec::<isize>::arbitrary()),), (SortedShrinker::new(isize::shrink()),))
+        .property(|xs| xs.windows(2).all(|w| w[0] <= w[1]));
+    quickcheck(prop);
+}
+
+#[test]
+fn unordered_collections_shrink_in_canonical_order() {
+    fn sorted_sets(sets: Vec<HashSet<u8>>) -> Vec<Vec<u8>> {
+        sets.into_iter().map(|set| { let mut v = set.into_iter().collect::<Vec<_>>(); v.sort(); v }).collect()
+    }
+    // Each set has its own random hasher, so the two iterate in different orders.
+    let set = (0..20).collect::<HashSet<u8>>();
+    let other = (0..20).rev().collect::<HashSet<u8>>();
+    let shrinks = sorted_sets(HashSet::<u8>::shrink().shrink(&set).collect());
+    assert_eq!(shrinks, sorted_sets(HashSet::<u8>::shrink().shrink(&other).collect()));
+    assert_eq!(shrinks[1].len(), 10);
+
+    fn sorted_maps(maps: Vec<HashMap<u8, u8>>) -> Vec<BTreeMap<u8, u8>> {
+        maps.into_iter().map(|map| map.into_iter().collect()).collect()
+    }
+    let map = (0..20).map(|k| (k, k)).collect::<HashMap<u8, u8>>();
+    let other = (0..20).rev().map(|k| (k, k)).collect::<HashMap<u8, u8>>();
+    let shrinks = sorted_maps(HashMap::<u8, u8>::shrink().shrink(&map).collect());
+    assert_eq!(shrinks, sorted_maps(HashMap::<u8, u8>::shrink().shrink(&other).collect()));
+    assert_eq!(shrinks[1].len(), 10);
+
+    let heap = vec![3u8, 1, 2].into_iter().collect::<BinaryHeap<_>>();
+    let other = vec![1u8, 2, 3].into_iter().collect::<BinaryHeap<_>>();
+    let shrinks = BinaryHeap::shrink().shrink(&heap).map(BinaryHeap::into_sorted_vec).collect::<Vec<_>>();
+    assert_eq!(shrinks, BinaryHeap::shrink().shrink(&other).map(BinaryHeap::into_sorted_vec).collect::<Vec<_>>());
+}
+
+#[test]
+fn permutation_shrinks_toward_source() {
+    fn prop(xs: &[usize]) -> bool { xs[0] == 0 || xs[1] == 1 }
+    fn inversions(xs: &[usize]) -> usize {
+        (0..xs.len()).map(|i| (i + 1..xs.len()).filter(|&j| xs[i] > xs[j]).count()).sum()
+    }
+
+    let source = (0..10).collect::<Vec<usize>>();
+    let shrinker = PermutationShrinker::new(source.clone());
+    let property = Property::<(Vec<usize>,)>
+        ::for_all_shrink((permutation_of(source.clone()),), (shrinker.clone(),))
+        .property(|xs| prop(&xs));
+
+    let result = quicktest(property);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => {
+            let xs = input.trim_start_matches("([").trim_end_matches("],)")
+                .split(", ").map(|x| x.parse().unwrap()).collect::<Vec<usize>>();
+            let mut sorted = xs.clone();
+            sorted.sort();
+            assert_eq!(sorted, source);
+            assert!(!prop(&xs));
+            // Every candidate closer to the source passes, so shrinking went as far as it could.
+            for candidate in shrinker.shrink(&xs) {
+                assert!(inversions(&candidate) < inversions(&xs));
+                assert!(prop(&candidate), "{:?} could shrink to {:?}", xs, candidate);
+            }
+        },
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+    assert_eq!(shrinker.shrink(&vec![0, 1, 2]).count(), 0);
+    assert_eq!(shrinker.shrink(&(0..10).map(|x| x % 9).collect()).count(), 0);
+}
+
+#[test]
+fn subset_is_subset() {
+    let source = (0..20).collect::<BTreeSet<u8>>();
+    let prop = Property::<(BTreeSet<u8>,)>
+        ::for_all_shrink((subset_of(source.clone()),), (FromIteratorShrinker::subset(),))
+        .property(move |xs| xs.is_subset(&source));
+    quickcheck(prop);
+
+    let prop = Property::<(Vec<char>,)>
+        ::for_all_shrink((sublist_of(&['a', 'b', 'c', 'd']),), (FromIteratorShrinker::subset(),))
+        .property(|xs| xs.len() < 3);
+    match quicktest(prop) {
+        Err(QuickCheckError::Failure { ref input, .. }) =>
+            assert!(input.starts_with("(['") && input.matches(',').count() == 3, "Unexpected shrunk input {}", input),
+        result => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn map_shrinking_keeps_keys() {
+    fn prop(map: BTreeMap<u8, u8>) -> bool {
+        map.values().all(|&v| v < 10)
+    }
+    let result = quicktest(prop as fn(BTreeMap<u8, u8>) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) =>
+            assert!(input.starts_with("({") && input.ends_with(": 10},)") && input.matches(':').count() == 1,
+                    "Didn't get the expected shrunk result.  Instead found {:?}", result),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn hash_map_is_arbitrary() {
+    fn prop(map: HashMap<u8, Vec<bool>>) -> bool {
+        map.is_empty()
+    }
+    let result = quicktest(prop as fn(HashMap<u8, Vec<bool>>) -> bool);
+    match result {
+        // Keys aren't shrunk, so the map shrinks to a single entry with an empty value.
+        Err(QuickCheckError::Failure { ref input, .. }) => {
+            let key = input.trim_start_matches("({").trim_end_matches(": []},)");
+            assert!(key.parse::<u8>().is_ok(), "{}", input);
+        },
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Expr { Lit(u8), Add(Box<Expr>, Box<Expr>), Neg(Box<Expr>) }
+
+fn expr_subterms(expr: &Expr) -> Vec<Expr> {
+    match *expr {
+        Expr::Lit(_) => vec![],
+        Expr::Add(ref l, ref r) => vec![(**l).clone(), (**r).clone()],
+        Expr::Neg(ref e) => vec![(**e).clone()]
+    }
+}
+
+fn expr_rebuild(expr: &Expr, mut subterms: Vec<Expr>) -> Expr {
+    match *expr {
+        Expr::Lit(n) => Expr::Lit(n),
+        Expr::Add(..) => {
+            let r = subterms.pop().unwrap();
+            Expr::Add(Box::new(subterms.pop().unwrap()), Box::new(r))
+        },
+        Expr::Neg(_) => Expr::Neg(Box::new(subterms.pop().unwrap()))
+    }
+}
+
+#[test]
+fn recursive_expr_shrinks_to_subterm() {
+    let gen = recursive(
+        u8::arbitrary().map(Expr::Lit),
+        |expr| (expr.clone(), expr.clone(), bool::arbitrary()).map(|(l, r, add)| match add {
+            true => Expr::Add(Box::new(l), Box::new(r)),
+            false => Expr::Neg(Box::new(l))
+        })
+    );
+
+    let prop = Property::<(Expr,)>
+        ::for_all_shrink((gen,), (SubtermShrinker::new(expr_subterms, expr_rebuild),))
+        .property(|expr| match expr { Expr::Neg(_) => false, _ => true });
+
+    let result = QuickCheck::new().tests(1000).quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) =>
+            assert!(input.starts_with("(Neg(Lit("), "Didn't get the expected shrunk result.  Instead found {:?}", result),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn json_shrinks_to_scalar() {
+    let prop = Property::<(Json,)>
+        ::for_all_shrink((JsonGenerator::new(),), (JsonShrinker::new(),))
+        .property(|json| !json.to_string().contains("true"));
+
+    let result = QuickCheck::new().tests(1000).quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(Bool(true),)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn regex_shrinks_within_language() {
+    let (gen, shrinker) = regex("[a-z]+@[a-z]+\\.com");
+    let prop = Property::<(String,)>
+        ::for_all_shrink((gen,), (shrinker,))
+        .property(|email: String| email.find('@').unwrap() < 3);
+
+    let result = QuickCheck::new().quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(\"aaa@a.com\",)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn grammar_shrinks_to_smaller_derivation() {
+    let grammar = Grammar::from_bnf("
+        <expr> ::= <term> | <expr> '+' <term>
+        <term> ::= 'x' | '(' <expr> ')'
+    ").unwrap();
+    let prop = Property::<(_,)>
+        ::for_all_shrink((grammar.generator(),), (grammar.shrinker(),))
+        .property(|sentence| !sentence.to_string().contains('+'));
+
+    let result = QuickCheck::new().quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(\"x+x\",)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn edge_cases_find_overflow() {
+    let prop = Property::<(i32,)>
+        ::for_all_shrink((IntegerGenerator::<i32>::new().edge_cases(0.5),), (IntegerShrinker::<i32>::new(),))
+        .property(|n: i32| n.checked_abs().is_some());
+
+    let result = QuickCheck::new().tests(1000).quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(-2147483648,)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn wrappers_shrink_through_to_inner_values() {
+    fn prop(b: Box<u8>, _: Rc<u8>, _: RefCell<u8>, _: Wrapping<u8>, _: Reverse<u8>) -> bool {
+        *b < 10
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(Box<u8>, Rc<u8>, RefCell<u8>, Wrapping<u8>, Reverse<u8>) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) =>
+            assert_eq!(input, "(10, 0, RefCell { value: 0 }, 0, Reverse(0))"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn arrays_shrink_element_wise() {
+    fn prop(key: [u8; 32], _: [bool; 0]) -> bool {
+        key[5] < 10
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn([u8; 32], [bool; 0]) -> bool);
+    let mut expected = [0u8; 32];
+    expected[5] = 10;
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, &format!("({:?}, [])", expected)),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn std_types_shrink() {
+    fn prop(_: Ordering, d: Duration) -> bool {
+        d < Duration::from_secs(1)
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(Ordering, Duration) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(Equal, 1s)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn ranges_are_well_formed() {
+    fn prop(r: Range<char>, ri: RangeInclusive<i8>, n: NonZeroI32) -> bool {
+        r.start <= r.end && ri.start() <= ri.end() && n.get() != 0
+    }
+
+    quickcheck(prop as fn(Range<char>, RangeInclusive<i8>, NonZeroI32) -> bool);
+}
+
+#[test]
+fn ranges_and_non_zero_integers_shrink() {
+    fn prop(r: Range<u8>, ri: RangeInclusive<u8>, n: NonZeroI32) -> bool {
+        r.len() < 3 || ri.start() == ri.end() || n.get() < 5
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(Range<u8>, RangeInclusive<u8>, NonZeroI32) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(0..3, 0..=1, 5)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+
+    let shrinks = NonZeroI32::shrink().shrink(&NonZeroI32::new(-5).unwrap()).map(NonZeroI32::get).collect::<Vec<_>>();
+    assert_eq!(shrinks, vec![5, -3, -4]);
+}
+
+#[test]
+fn u128_shrinks_to_boundary() {
+    let prop = Property::<(u128,)>
+        ::for_all_shrink((UnsignedIntegerGenerator::<u128>::new().edge_cases(0.5),), (u128::shrink(),))
+        .property(|n| n <= u64::max_value() as u128);
+
+    let result = QuickCheck::new().tests(1000).quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(18446744073709551616,)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn modifiers_keep_their_invariants() {
+    fn prop(p: Positive<i32>, nn: NonNegative<i8>, nz: NonZero<i64>, s: Small<u8>, v: NonEmpty<Vec<u8>>, sv: Sorted<Vec<u8>>, a: Ascii) -> bool {
+        p.0 > 0 && nn.0 >= 0 && nz.0 != 0 && s.0 <= 100 && !v.0.is_empty()
+            && sv.0.windows(2).all(|w| w[0] <= w[1]) && a.0.is_ascii()
+    }
+
+    quickcheck(prop as fn(Positive<i32>, NonNegative<i8>, NonZero<i64>, Small<u8>, NonEmpty<Vec<u8>>, Sorted<Vec<u8>>, Ascii) -> bool);
+}
+
+#[test]
+fn modifiers_shrink_within_their_invariants() {
+    fn prop(p: Positive<i32>, v: NonEmpty<Vec<u8>>, l: Large<u64>) -> bool {
+        p.0 < 1 || v.0.is_empty() || l.0 < 1000
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(Positive<i32>, NonEmpty<Vec<u8>>, Large<u64>) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(Positive(1), NonEmpty([0]), Large(1000))"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn small_integers_stay_below_the_square_root_of_the_size() {
+    fn prop(n: Small<i64>, u: Small<u64>) -> bool {
+        n.0.abs() <= 100 && u.0 <= 100
+    }
+
+    QuickCheck::new().max_size(10000).quickcheck(prop as fn(Small<i64>, Small<u64>) -> bool);
+}
+
+#[test]
+fn large_128_bit_integers_reach_beyond_64_bits() {
+    fn prop(n: Large<i128>, u: Large<u128>) -> bool {
+        n.0.checked_abs().map_or(false, |n| n <= u64::max_value() as i128) && u.0 <= u64::max_value() as u128
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(Large<i128>, Large<u128>) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { .. }) => (),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn properties_take_up_to_sixteen_arguments() {
+    fn prop(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8,
+            i: u8, j: u8, k: u8, l: u8, m: u8, n: u8, o: u8, p: u8) -> bool {
+        [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o].iter().any(|&x| x > 100) || p == 0
+    }
+
+    let result = QuickCheck::new().quicktest(prop as fn(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EvenLength(Vec<u8>);
+
+#[test]
+fn shrink_combinators_preserve_invariants() {
+    let vecs = FromIteratorShrinker::<Vec<u8>, _>::new(u8::shrink());
+    let value = vec![3, 1, 4, 1, 5, 9];
+    let candidates = vecs.shrink(&value).collect::<Vec<_>>();
+
+    let mapped = vecs.clone().map(EvenLength, |xs: &EvenLength| xs.0.clone());
+    assert_eq!(mapped.shrink(&EvenLength(value.clone())).map(|xs| xs.0).collect::<Vec<_>>(), candidates);
+
+    let even = vecs.clone().filter(|xs: &Vec<u8>| xs.len() % 2 == 0);
+    let even_candidates = even.shrink(&value).collect::<Vec<_>>();
+    assert_eq!(even_candidates, candidates.iter().filter(|xs| xs.len() % 2 == 0).cloned().collect::<Vec<_>>());
+    assert!(even_candidates.len() < candidates.len());
+
+    let at_least_five = FromIteratorShrinker::<Vec<u8>, _>::with_min_len(u8::shrink(), 5);
+    let chained = vecs.clone().chain(at_least_five.clone()).shrink(&value).collect::<Vec<_>>();
+    assert_eq!(chained[..candidates.len()], candidates[..]);
+    assert_eq!(chained[candidates.len()..], at_least_five.shrink(&value).collect::<Vec<_>>()[..]);
+
+    assert_eq!(vecs.clone().limit(3).shrink(&value).collect::<Vec<_>>(), candidates[..3].to_vec());
+
+    let prop = Property::<(EvenLength,)>
+        ::for_all_shrink(
+            (Vec::<u8>::arbitrary().map(|mut xs: Vec<u8>| { xs.truncate(xs.len() / 2 * 2); EvenLength(xs) }),),
+            (mapped.filter(|xs: &EvenLength| xs.0.len() % 2 == 0),))
+        .property(|xs: EvenLength| xs.0.len() < 4);
+
+    let result = quicktest(prop);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => {
+            // One comma between each pair of elements, and one after the tuple's only element.
+            let len = input.matches(',').count();
+            assert!(len >= 4 && len % 2 == 0, "Unexpected shrunk input {}", input)
+        },
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Port(u16);
+
+fn port_number(port: &Port) -> u16 { port.0 }
+
+impl Arbitrary for Port {
+    type Generator = MappedGenerator<UnsignedIntegerGenerator<u16>, fn(u16) -> Port>;
+    type Shrink = MappedShrinker<UnsignedIntegerShrinker<u16>, fn(u16) -> Port, fn(&Port) -> u16>;
+
+    fn arbitrary() -> Self::Generator { u16::arbitrary().map(Port as fn(u16) -> Port) }
+
+    fn shrink() -> Self::Shrink {
+        u16::shrink().shrink_towards(1024).map(Port as fn(u16) -> Port, port_number as fn(&Port) -> u16)
+    }
+}
+
+#[test]
+fn integer_shrinkers_halve_the_distance() {
+    assert_eq!(IntegerShrinker::<i32>::new().shrink(&100).collect::<Vec<_>>(), vec![0, 50, 75, 88, 94, 97, 99]);
+    assert_eq!(IntegerShrinker::<i32>::new().shrink(&-100).collect::<Vec<_>>(), vec![0, 100, -50, -75, -88, -94, -97, -99]);
+    assert_eq!(IntegerShrinker::<i32>::new().shrink(&0).count(), 0);
+    assert_eq!(UnsignedIntegerShrinker::<u8>::new().shrink(&255).collect::<Vec<_>>(), vec![0, 128, 192, 224, 240, 248, 252, 254]);
+
+    // The extremes of the type shrink without overflowing.  `MIN` has no positive counterpart.
+    assert_eq!(IntegerShrinker::<i8>::new().shrink(&i8::min_value()).collect::<Vec<_>>(), vec![0, -64, -96, -112, -120, -124, -126, -127]);
+    assert_eq!(IntegerShrinker::<i8>::new().shrink(&i8::max_value()).collect::<Vec<_>>(), vec![0, 64, 96, 112, 120, 124, 126]);
+    let shrinks = IntegerShrinker::<i64>::new().shrink(&i64::min_value()).collect::<Vec<_>>();
+    assert_eq!(shrinks.len(), 64);
+    assert!(shrinks.iter().all(|&x| x > i64::min_value() && x <= 0));
+
+    fn prop(n: Large<i32>) -> bool { n.0 < 1000 }
+    let result = QuickCheck::new().quicktest(prop as fn(Large<i32>) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(Large(1000),)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+}
+
+#[test]
+fn shrinkers_move_toward_their_target() {
+    fn prop(port: Port) -> bool { port.0 < 10 }
+    let result = QuickCheck::new().quicktest(prop as fn(Port) -> bool);
+    match result {
+        Err(QuickCheckError::Failure { ref input, .. }) => assert_eq!(input, "(Port(1024),)"),
+        _ => assert!(false, "Test didn't fail: {:?}", result)
+    }
+
+    let shrinks = IntegerShrinker::new().shrink_towards(-10i32).shrink(&-20).collect::<Vec<_>>();
+    assert_eq!(shrinks, vec![-10, 0, -15, -18, -19]);
+    let shrinks = UnsignedIntegerShrinker::new().shrink_towards(100u8).shrink(&10).collect::<Vec<_>>();
+    assert_eq!(shrinks, vec![100, 55, 32, 21, 15, 12, 11]);
+    let shrinks = BigInt::shrink().shrink(&BigInt::from(-20)).collect::<Vec<_>>();
+    assert_eq!(shrinks, vec![0, 20, -10, -15, -18, -19].into_iter().map(BigInt::from).collect::<Vec<_>>());
+
+    let shrinks = FloatShrinker::new().shrink_towards(1.0f64).shrink(&4.5).collect::<Vec<_>>();
+    assert_eq!(&shrinks[..3], &[1.0, 4.0, 2.75]);
+    assert!(shrinks.iter().all(|x| *x >= 1.0 && *x < 4.5));
+    assert_eq!(FloatShrinker::new().shrink_towards(1.0f64).shrink(&f64::INFINITY).count(), 0);
+    assert_eq!(FloatShrinker::<f64>::new().shrink(&f64::NAN).count(), 0);
+
+    let shrinks = BigUint::shrink().shrink_towards(BigUint::from(8u32)).shrink(&BigUint::from(2u32)).collect::<Vec<_>>();
+    assert_eq!(shrinks, vec![8u32, 5, 3].into_iter().map(BigUint::from).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn floats_cannot_shrink_toward_infinity() {
+    FloatShrinker::new().shrink_towards(f64::INFINITY);
+}
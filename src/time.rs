@@ -0,0 +1,127 @@
+use arbitrary::Arbitrary;
+use generate::{Generator, GenerateCtx, UnsignedIntegerGenerator};
+use shrink::{MappedShrinker, UnsignedIntegerShrinker, DefaultShrinker};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Generates durations whose whole seconds grow with the size, with any number of nanoseconds.
+#[derive(Copy, Clone)]
+pub struct DurationGenerator;
+
+impl Generator for DurationGenerator {
+    type Output = Duration;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> Duration {
+        let secs = UnsignedIntegerGenerator::<u64>::new().generate(ctx);
+        Duration::new(secs, ctx.rng.gen_range(0, NANOS_PER_SEC))
+    }
+}
+
+fn duration_from_parts((secs, nanos): (u64, u32)) -> Duration { Duration::new(secs, nanos) }
+fn duration_to_parts(duration: &Duration) -> (u64, u32) { (duration.as_secs(), duration.subsec_nanos()) }
+
+/// Shrinks durations toward zero, shrinking the whole seconds and the nanoseconds separately.
+pub type DurationShrinker = MappedShrinker<
+    (UnsignedIntegerShrinker<u64>, UnsignedIntegerShrinker<u32>),
+    fn((u64, u32)) -> Duration,
+    fn(&Duration) -> (u64, u32)>;
+
+impl Arbitrary for Duration {
+    type Generator = DurationGenerator;
+    type Shrink = DurationShrinker;
+
+    fn arbitrary() -> Self::Generator { DurationGenerator }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            (UnsignedIntegerShrinker::new(), UnsignedIntegerShrinker::new()),
+            duration_from_parts as fn((u64, u32)) -> Duration,
+            duration_to_parts as fn(&Duration) -> (u64, u32)
+        )
+    }
+}
+
+/// Generates times before and after the Unix epoch, as far from it as `DurationGenerator`
+/// reaches.  Times the platform can't represent are replaced by the epoch itself.
+#[derive(Copy, Clone)]
+pub struct SystemTimeGenerator;
+
+impl Generator for SystemTimeGenerator {
+    type Output = SystemTime;
+
+    fn generate<R: rand::Rng>(&self, ctx: &mut GenerateCtx<R>) -> SystemTime {
+        let before_epoch = ctx.rng.gen();
+        system_time_from_parts((before_epoch, DurationGenerator.generate(ctx)))
+    }
+}
+
+fn system_time_from_parts((before_epoch, duration): (bool, Duration)) -> SystemTime {
+    match before_epoch {
+        true => UNIX_EPOCH.checked_sub(duration),
+        false => UNIX_EPOCH.checked_add(duration)
+    }.unwrap_or(UNIX_EPOCH)
+}
+
+fn system_time_to_parts(time: &SystemTime) -> (bool, Duration) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (false, duration),
+        Err(err) => (true, err.duration())
+    }
+}
+
+/// Shrinks times toward the Unix epoch, and times before it to times after it.
+pub type SystemTimeShrinker = MappedShrinker<
+    (DefaultShrinker<bool>, DurationShrinker),
+    fn((bool, Duration)) -> SystemTime,
+    fn(&SystemTime) -> (bool, Duration)>;
+
+impl Arbitrary for SystemTime {
+    type Generator = SystemTimeGenerator;
+    type Shrink = SystemTimeShrinker;
+
+    fn arbitrary() -> Self::Generator { SystemTimeGenerator }
+
+    fn shrink() -> Self::Shrink {
+        MappedShrinker::new(
+            (DefaultShrinker::new(), Duration::shrink()),
+            system_time_from_parts as fn((bool, Duration)) -> SystemTime,
+            system_time_to_parts as fn(&SystemTime) -> (bool, Duration)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use shrink::Shrink;
+    use rand;
+
+    #[test]
+    fn gen_durations() {
+        let mut rng = rand::thread_rng(); let mut ctx = GenerateCtx::new(&mut rng, 10);
+        for _ in 0..100 {
+            let duration = DurationGenerator.generate(&mut ctx);
+            assert!(duration.as_secs() <= 10 && duration.subsec_nanos() < NANOS_PER_SEC);
+        }
+    }
+
+    #[test]
+    fn durations_shrink_toward_zero() {
+        let shrinks = Duration::shrink().shrink(&Duration::new(4, 10)).collect::<Vec<_>>();
+        assert_eq!(shrinks[0], Duration::new(0, 10));
+        assert!(shrinks.iter().all(|d| *d < Duration::new(4, 10)));
+    }
+
+    #[test]
+    fn system_times_shrink_toward_epoch() {
+        let before = UNIX_EPOCH - Duration::new(3, 0);
+        let shrinks = SystemTime::shrink().shrink(&before).collect::<Vec<_>>();
+        assert_eq!(shrinks[0], UNIX_EPOCH + Duration::new(3, 0));
+        assert!(shrinks.contains(&UNIX_EPOCH));
+    }
+}